@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use crate::{Symbol, State, Ranked, Product};
+use crate::bottom_up::Indexed;
+use crate::utils::combinations;
+use super::{Automaton, Conjuction, Clause};
+
+impl<F: Symbol, Q: State, I: Clone> Automaton<F, Q, I> {
+    /// Compute the union of two alternating automata.
+    ///
+    /// The states of `self` (resp. `other`) are tagged with the index `0` (resp. `1`) so that
+    /// states with the same name are not merged.
+    pub fn or(&self, other: &Automaton<F, Q, I>) -> Automaton<F, Indexed<Q>, I> {
+        let mut aut = self.map_states(|q| Indexed(q.clone(), 0));
+
+        for (q, clauses) in other.state_clauses.iter() {
+            let q = Indexed(q.clone(), 1);
+            for (f, clause) in clauses.iter() {
+                for conjunction in clause.iter() {
+                    aut.add(&q, f, conjunction.iter().map(|(i, p)| (i.clone(), Indexed(p.clone(), 1))).collect());
+                }
+            }
+        }
+
+        for q in other.initial_states.iter() {
+            aut.set_initial(Indexed(q.clone(), 1));
+        }
+
+        aut
+    }
+
+    /// Compute the intersection of two alternating automata.
+    ///
+    /// Each state of the resulting automaton is a conjunction of tagged states, where the states
+    /// of `self` (resp. `other`) are tagged with the index `0` (resp. `1`).
+    /// A state `q` of `self` is represented by the singleton `[q.0]`, and the initial states are
+    /// the conjunctions `[p.0, q.1]` of initial states of each automaton.
+    pub fn and(&self, other: &Automaton<F, Q, I>) -> Automaton<F, Product<Indexed<Q>>, I> {
+        fn tag<Q: State, I: Clone>(conjunction: &Conjuction<Q, I>, index: usize) -> Conjuction<Product<Indexed<Q>>, I> {
+            conjunction.iter().map(|(i, q)| (i.clone(), Product::from(vec![Indexed(q.clone(), index)]))).collect()
+        }
+
+        let mut aut = Automaton::new();
+
+        for (index, a) in [self, other].iter().enumerate() {
+            for (q, clauses) in a.state_clauses.iter() {
+                let q = Product::from(vec![Indexed(q.clone(), index)]);
+                for (f, clause) in clauses.iter() {
+                    for conjunction in clause.iter() {
+                        aut.add(&q, f, tag(conjunction, index));
+                    }
+                }
+            }
+        }
+
+        for p in self.initial_states.iter() {
+            for q in other.initial_states.iter() {
+                let initial = Product::from(vec![Indexed(p.clone(), 0), Indexed(q.clone(), 1)]);
+                for (f, p_clause) in self.clauses_for_state(p) {
+                    if let Some(q_clause) = other.state_clauses.get(q).and_then(|clauses| clauses.get(f)) {
+                        for p_conjunction in p_clause.iter() {
+                            for q_conjunction in q_clause.iter() {
+                                let mut conjunction = tag(p_conjunction, 0);
+                                conjunction.extend(tag(q_conjunction, 1));
+                                aut.add(&initial, f, conjunction);
+                            }
+                        }
+                    }
+                }
+
+                aut.set_initial(initial);
+            }
+        }
+
+        aut
+    }
+}
+
+/// Negate the given clause of a symbol of the given arity, using De Morgan's laws.
+///
+/// The negation of each literal `(i, q)` is `(i, ¬q)`, where `¬q` is represented by `q` itself,
+/// and the result is put back in disjunctive normal form.
+/// Literals whose index is out of range are never satisfied, so their conjunctions are ignored.
+fn negate_clause<Q: State>(clause: &Clause<Q, u32>, arity: usize) -> Clause<Q, u32> {
+    let conjunctions: Vec<&Conjuction<Q, u32>> = clause.iter().filter(|conjunction| {
+        conjunction.iter().all(|(i, _)| (*i as usize) < arity)
+    }).collect();
+
+    // Pick one negated literal in each conjunction.
+    combinations(&conjunctions, |conjunction| conjunction.iter().cloned()).map(|mut literals| {
+        literals.sort_by_key(|(i, _)| *i);
+        literals.dedup();
+        literals
+    }).collect()
+}
+
+impl<F: Symbol + Ranked, Q: State> Automaton<F, Q, u32> {
+    /// Compute the dual automaton over the given alphabet.
+    ///
+    /// Each state `q` of the dual automaton recognizes the complement of the language of `q` in
+    /// `self`, relative to the terms of the given alphabet.
+    /// The initial states are left unchanged.
+    pub fn dual<'a, A: IntoIterator<Item=&'a F>>(&self, alphabet: A) -> Automaton<F, Q, u32> where F: 'a {
+        let alphabet: Vec<&F> = alphabet.into_iter().collect();
+        let mut states: HashSet<&Q> = self.states().collect();
+        states.extend(self.initial_states.iter());
+        for clauses in self.state_clauses.values() {
+            for clause in clauses.values() {
+                states.extend(clause.iter().flat_map(|conjunction| conjunction.iter().map(|(_, q)| q)));
+            }
+        }
+
+        let mut aut = Automaton::new();
+        for q in states {
+            for f in alphabet.iter() {
+                let clause = match self.state_clauses.get(q).and_then(|clauses| clauses.get(*f)) {
+                    Some(clause) => negate_clause(clause, f.arity()),
+                    None => vec![Vec::new()]
+                };
+
+                for conjunction in clause {
+                    aut.add(q, *f, conjunction);
+                }
+            }
+        }
+
+        for q in self.initial_states.iter() {
+            aut.set_initial(q.clone());
+        }
+
+        aut
+    }
+
+    /// Compute the complement of the automaton over the given alphabet.
+    ///
+    /// The states of the resulting automaton are conjunctions of states of the dual automaton.
+    /// A state `q` is represented by the singleton `[q]`, and the unique initial state is the
+    /// conjunction of all the initial states of `self`.
+    pub fn negate<'a, A: IntoIterator<Item=&'a F>>(&self, alphabet: A) -> Automaton<F, Product<Q>, u32> where F: 'a {
+        let alphabet: Vec<&F> = alphabet.into_iter().collect();
+        let dual = self.dual(alphabet.iter().cloned());
+
+        let mut aut = dual.map_states(|q| Product::from(vec![q.clone()]));
+        aut.initial_states.clear();
+
+        let initial_states: Vec<&Q> = dual.initial_states.iter().collect();
+        let initial = Product::from(initial_states.clone());
+        if initial.len() != 1 {
+            for f in alphabet.iter() {
+                let no_clause = Vec::new();
+                let clauses: Vec<&Clause<Q, u32>> = initial_states.iter().map(|q| {
+                    dual.state_clauses.get(*q).and_then(|clauses| clauses.get(*f)).unwrap_or(&no_clause)
+                }).collect();
+
+                // Pick one conjunction in the clause of each initial state.
+                for conjunctions in combinations(&clauses, |clause| clause.iter()) {
+                    let conjunction = conjunctions.into_iter().flat_map(|conjunction| {
+                        conjunction.iter().map(|(i, q)| (*i, Product::from(vec![q.clone()])))
+                    }).collect();
+                    aut.add(&initial, *f, conjunction);
+                }
+            }
+        }
+
+        aut.set_initial(initial);
+        aut
+    }
+}
@@ -0,0 +1,72 @@
+use terms::Term;
+use crate::{Symbol, State, Ranked};
+use crate::utils::combinations;
+use super::{Automaton, types::Types};
+
+/// Checks if every state of the type `a` is also in the type `b`.
+fn is_subtype(a: &[bool], b: &[bool]) -> bool {
+    a.iter().zip(b.iter()).all(|(a, b)| !*a || *b)
+}
+
+impl<F: Symbol + Ranked, Q: State> Automaton<F, Q, u32> {
+    /// Return a term recognized by the automaton, if any.
+    ///
+    /// This computes the least fixpoint of the *types* of terms, where the type of a term is the
+    /// set of states recognizing it, without building the equivalent bottom-up automaton.
+    /// Since clauses are positive, replacing a sub-term by another of larger type can only
+    /// enlarge the type of the whole term. Hence only the maximal types are kept, in an
+    /// antichain, each with a witness term.
+    ///
+    /// The types are computed by rounds, each combining at least one type found during the
+    /// previous round, so the returned term has minimal height.
+    pub fn witness(&self) -> Option<Term<F>> {
+        let types = Types::new(self);
+        let initial_states: Vec<usize> = self.initial_states.iter().filter_map(|q| types.index(q)).collect();
+        let symbols: Vec<&F> = types.symbols().collect();
+
+        // Maximal types found so far, with their witness and the round they were found in.
+        let mut antichain: Vec<(Vec<bool>, Term<F>, usize)> = Vec::new();
+        let mut round = 0;
+        loop {
+            let mut found = Vec::new();
+            for f in symbols.iter() {
+                let positions = vec![(); f.arity()];
+                let elements = 0..antichain.len();
+                for tuple in combinations(&positions, |_| elements.clone()) {
+                    // Only combine types found in the previous round with older ones.
+                    if round > 0 && !tuple.iter().any(|i| antichain[*i].2 == round - 1) {
+                        continue
+                    }
+
+                    let sub_types: Vec<&[bool]> = tuple.iter().map(|i| antichain[*i].0.as_slice()).collect();
+                    let ty = types.post(f, &sub_types);
+                    if antichain.iter().chain(found.iter()).any(|(other, _, _): &(Vec<bool>, _, _)| is_subtype(&ty, other)) {
+                        continue
+                    }
+
+                    let sub_terms = tuple.iter().map(|i| antichain[*i].1.clone()).collect();
+                    let term = Term::new((*f).clone(), sub_terms);
+                    if initial_states.iter().any(|q| ty[*q]) {
+                        return Some(term)
+                    }
+
+                    found.retain(|(other, _, _)| !is_subtype(other, &ty));
+                    found.push((ty, term, round));
+                }
+            }
+
+            if found.is_empty() {
+                return None
+            }
+
+            antichain.retain(|(other, _, _)| !found.iter().any(|(ty, _, _)| is_subtype(other, ty)));
+            antichain.extend(found);
+            round += 1;
+        }
+    }
+
+    /// Checks if the language recognized by the automaton is empty.
+    pub fn is_empty(&self) -> bool {
+        self.witness().is_none()
+    }
+}
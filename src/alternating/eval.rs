@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use terms::Term;
+use crate::{Symbol, State};
+use super::Automaton;
+
+impl<F: Symbol, Q: State> Automaton<F, Q, u32> {
+    /// Checks if the given state recognizes the given term.
+    ///
+    /// The clauses are evaluated top-down: the state recognizes `f(t0, ..., tn)` if one of the
+    /// conjunctions of its clause for `f` is satisfied, where `(i, p)` is satisfied if `p`
+    /// recognizes `ti`.
+    /// Results are memoized for each (state, sub-term) pair, so that each sub-term is only
+    /// evaluated once per state.
+    pub fn state_accepts(&self, q: &Q, term: &Term<F>) -> bool {
+        let mut memo = HashMap::new();
+        self.eval(q, term, &mut memo)
+    }
+
+    /// Checks if the given term is recognized by the automaton, that is, by one of its initial
+    /// states.
+    pub fn accepts(&self, term: &Term<F>) -> bool {
+        let mut memo = HashMap::new();
+        self.initial_states.iter().any(|q| self.eval(q, term, &mut memo))
+    }
+
+    /// Sub-terms are identified by their address, which also avoids comparing them.
+    fn eval<'a>(&'a self, q: &'a Q, term: &Term<F>, memo: &mut HashMap<(&'a Q, *const Term<F>), bool>) -> bool {
+        let key = (q, term as *const Term<F>);
+        if let Some(result) = memo.get(&key) {
+            return *result
+        }
+
+        let result = match self.state_clauses.get(q).and_then(|clauses| clauses.get(term.symbol())) {
+            Some(clause) => clause.iter().any(|conjunction| {
+                conjunction.iter().all(|(i, p)| {
+                    match term.sub_terms().get(*i as usize) {
+                        Some(sub_term) => self.eval(p, sub_term, memo),
+                        None => false
+                    }
+                })
+            }),
+            None => false
+        };
+
+        memo.insert(key, result);
+        result
+    }
+}
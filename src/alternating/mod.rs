@@ -1,6 +1,15 @@
 use std::collections::{HashMap, HashSet};
-use crate::{Symbol, Label, State};
-use crate::bottom_up;
+use terms::Term;
+use crate::{Symbol, Label, State, Ranked, Language, Product, NoLabel, StateSet};
+use crate::bottom_up::{self, Indexed};
+
+mod types;
+mod eval;
+mod boolean;
+mod emptiness;
+mod simplify;
+
+pub use simplify::Simplification;
 
 /// The empty conjunction is True.
 pub type Conjuction<Q, I> = Vec<(I, Q)>;
@@ -63,6 +72,11 @@ impl<F: Symbol, Q: State, I> Automaton<F, Q, I> {
         }
     }
 
+    /// Return an iterator over the initial states.
+    pub fn initial_states(&self) -> std::collections::hash_set::Iter<'_, Q> {
+        self.initial_states.iter()
+    }
+
     pub fn is_initial(&self, q: &Q) -> bool {
         self.initial_states.contains(q)
     }
@@ -112,6 +126,9 @@ impl<'a, F: Symbol, Q: State, L: Label> From<&'a bottom_up::Automaton<F, Q, L>>
     fn from(bottom_up: &'a bottom_up::Automaton<F, Q, L>) -> Automaton<F, Q, u32> {
         let mut alternating = Automaton::new();
 
+        // alternating automata have no epsilon transitions.
+        let bottom_up = bottom_up.without_epsilons();
+
         // add all transitions.
         for (conf, _, q) in bottom_up.transitions() {
             alternating.add_transition(conf, q)
@@ -125,3 +142,56 @@ impl<'a, F: Symbol, Q: State, L: Label> From<&'a bottom_up::Automaton<F, Q, L>>
         alternating
     }
 }
+
+impl<'a, F: Symbol + Ranked, Q: State + Ord> From<&'a Automaton<F, Q, u32>> for bottom_up::Automaton<F, StateSet<Q>, NoLabel> {
+    /// Convert an alternating automaton into a non-deterministic bottom-up automaton.
+    ///
+    /// Each state of the bottom-up automaton is the set of alternating states recognizing the
+    /// same terms, and only the sets reachable from the constants are created.
+    /// The final states are the sets containing an initial state.
+    fn from(alternating: &'a Automaton<F, Q, u32>) -> bottom_up::Automaton<F, StateSet<Q>, NoLabel> {
+        let mut bottom_up = bottom_up::Automaton::new();
+        let mut sets: Vec<StateSet<Q>> = Vec::new();
+
+        types::Types::new(alternating).explore(|types, f, sub_types, ty, new| {
+            if new {
+                let set: StateSet<Q> = types.states(ty).cloned().collect();
+                if set.iter().any(|q| alternating.is_initial(q)) {
+                    bottom_up.set_final(set.clone());
+                }
+                sets.push(set);
+            }
+
+            let states = sub_types.iter().map(|i| sets[*i].clone()).collect();
+            bottom_up.add(bottom_up::Configuration(f.clone(), states), NoLabel, sets[ty].clone());
+            true
+        });
+
+        bottom_up
+    }
+}
+
+impl<F: Symbol + Ranked, Q: State> Language<F> for Automaton<F, Q, u32> {
+    type Intersection = Automaton<F, Product<Indexed<Q>>, u32>;
+    type Union = Automaton<F, Indexed<Q>, u32>;
+
+    fn accepts(&self, term: &Term<F>) -> bool {
+        Automaton::accepts(self, term)
+    }
+
+    fn witness(&self) -> Option<Term<F>> {
+        Automaton::witness(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Automaton::is_empty(self)
+    }
+
+    fn intersect(&self, other: &Self) -> Automaton<F, Product<Indexed<Q>>, u32> {
+        self.and(other)
+    }
+
+    fn union(&self, other: &Self) -> Automaton<F, Indexed<Q>, u32> {
+        self.or(other)
+    }
+}
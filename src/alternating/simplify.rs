@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use crate::{Symbol, State};
+use super::{Automaton, Conjuction};
+
+/// What was removed by [`Automaton::simplify`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Simplification {
+    /// Number of removed conjunctions.
+    pub conjunctions: usize,
+
+    /// Number of removed states.
+    pub states: usize
+}
+
+/// Checks if every literal of `a` is also in `b`.
+///
+/// In that case `b` is subsumed by `a`: any term satisfying `b` also satisfies `a`.
+fn subsumes<Q: State, I: PartialEq>(a: &Conjuction<Q, I>, b: &Conjuction<Q, I>) -> bool {
+    a.iter().all(|literal| b.contains(literal))
+}
+
+impl<F: Symbol, Q: State, I: Clone + PartialEq> Automaton<F, Q, I> {
+    /// Simplify the clauses of the automaton, without changing its language.
+    ///
+    /// This removes
+    ///  - duplicate literals in conjunctions,
+    ///  - duplicate and subsumed conjunctions,
+    ///  - conjunctions using a state without clauses (which is false), until a fixpoint is
+    ///    reached,
+    ///  - states that are not reachable from the initial states.
+    pub fn simplify(&mut self) -> Simplification {
+        let mut removed = Simplification::default();
+
+        for clauses in self.state_clauses.values_mut() {
+            for clause in clauses.values_mut() {
+                for conjunction in clause.iter_mut() {
+                    let mut literals = Vec::with_capacity(conjunction.len());
+                    for literal in conjunction.drain(..) {
+                        if !literals.contains(&literal) {
+                            literals.push(literal)
+                        }
+                    }
+                    *conjunction = literals;
+                }
+
+                let len = clause.len();
+                let mut simplified: Vec<Conjuction<Q, I>> = Vec::new();
+                // Shorter conjunctions first, so that each one is only compared to those that
+                // may subsume it.
+                clause.sort_by_key(|conjunction| conjunction.len());
+                for conjunction in clause.drain(..) {
+                    if !simplified.iter().any(|other| subsumes(other, &conjunction)) {
+                        simplified.push(conjunction)
+                    }
+                }
+                *clause = simplified;
+                removed.conjunctions += len - clause.len();
+            }
+        }
+
+        // Remove false states.
+        loop {
+            let false_states: HashSet<Q> = self.state_clauses.iter().filter(|(_, clauses)| {
+                clauses.values().all(|clause| clause.is_empty())
+            }).map(|(q, _)| q.clone()).collect();
+
+            let mut changed = false;
+            for q in false_states.iter() {
+                self.state_clauses.remove(q);
+                removed.states += 1;
+            }
+
+            let states: HashSet<Q> = self.state_clauses.keys().cloned().collect();
+            for clauses in self.state_clauses.values_mut() {
+                for clause in clauses.values_mut() {
+                    let len = clause.len();
+                    clause.retain(|conjunction| conjunction.iter().all(|(_, q)| states.contains(q)));
+                    removed.conjunctions += len - clause.len();
+                    changed |= len != clause.len();
+                }
+                clauses.retain(|_, clause| !clause.is_empty());
+            }
+
+            if !changed && false_states.is_empty() {
+                break
+            }
+        }
+
+        // Remove unreachable states.
+        let mut reachable: HashSet<Q> = HashSet::new();
+        let mut pending: Vec<&Q> = self.initial_states.iter().collect();
+        while let Some(q) = pending.pop() {
+            if reachable.insert(q.clone()) {
+                for (_, clause) in self.clauses_for_state(q) {
+                    pending.extend(clause.iter().flat_map(|conjunction| conjunction.iter().map(|(_, p)| p)));
+                }
+            }
+        }
+
+        let len = self.state_clauses.len();
+        self.state_clauses.retain(|q, clauses| {
+            if !reachable.contains(q) {
+                removed.conjunctions += clauses.values().map(|clause| clause.len()).sum::<usize>();
+                false
+            } else {
+                true
+            }
+        });
+        removed.states += len - self.state_clauses.len();
+
+        removed
+    }
+}
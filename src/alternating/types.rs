@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use crate::{Symbol, State, Ranked};
+use crate::utils::explore_tuples;
+use super::Automaton;
+
+/// Clause where each state is replaced by its index, and each position by a `usize`.
+type IndexedClause = Vec<Vec<(usize, usize)>>;
+
+/// Bottom-up exploration of the *types* of the terms recognized by an alternating automaton.
+///
+/// The type of a term is the set of states recognizing it, represented by the characteristic
+/// vector of the set over the indexed states of the automaton.
+/// The type of `f(t0, ..., tn)` only depends on `f` and the types of the sub-terms.
+pub(super) struct Types<'a, F, Q> {
+    /// Indexed states of the automaton.
+    states: Vec<&'a Q>,
+
+    /// Index of each state.
+    indexes: HashMap<&'a Q, usize>,
+
+    /// Clauses of each symbol, with indexed states.
+    symbol_clauses: HashMap<&'a F, Vec<(usize, IndexedClause)>>,
+
+    /// Known types.
+    types: Vec<Vec<bool>>,
+
+    /// Index of each known type.
+    known: HashMap<Vec<bool>, usize>
+}
+
+impl<'a, F: Symbol + Ranked, Q: State> Types<'a, F, Q> {
+    pub fn new(aut: &'a Automaton<F, Q, u32>) -> Types<'a, F, Q> {
+        // States without clauses (which are false) may still appear in conjunctions.
+        let conjunction_states = aut.state_clauses.values().flat_map(|clauses| clauses.values()).flat_map(|clause| {
+            clause.iter().flat_map(|conjunction| conjunction.iter().map(|(_, q)| q))
+        });
+
+        let mut states: Vec<&Q> = Vec::new();
+        let mut indexes: HashMap<&Q, usize> = HashMap::new();
+        for q in aut.states().chain(aut.initial_states.iter()).chain(conjunction_states) {
+            if !indexes.contains_key(q) {
+                indexes.insert(q, states.len());
+                states.push(q);
+            }
+        }
+
+        let mut symbol_clauses: HashMap<&F, Vec<(usize, IndexedClause)>> = HashMap::new();
+        for (q, clauses) in aut.state_clauses.iter() {
+            for (f, clause) in clauses.iter() {
+                let clause = clause.iter().map(|conjunction| {
+                    conjunction.iter().map(|(i, p)| (*i as usize, indexes[p])).collect()
+                }).collect();
+                symbol_clauses.entry(f).or_default().push((indexes[q], clause))
+            }
+        }
+
+        Types {
+            states,
+            indexes,
+            symbol_clauses,
+            types: Vec::new(),
+            known: HashMap::new()
+        }
+    }
+
+    /// Return the states of the given type.
+    pub fn states(&self, ty: usize) -> impl Iterator<Item = &'a Q> + '_ {
+        self.types[ty].iter().zip(self.states.iter()).filter(|(b, _)| **b).map(|(_, q)| *q)
+    }
+
+    /// Symbols of the automaton.
+    pub fn symbols(&self) -> impl Iterator<Item = &'a F> + '_ {
+        self.symbol_clauses.keys().cloned()
+    }
+
+    /// Index of the given state, if it belongs to the automaton.
+    pub fn index(&self, q: &Q) -> Option<usize> {
+        self.indexes.get(q).cloned()
+    }
+
+    /// Type of the term `f(t0, ..., tn)` where each `ti` has the type `sub_types[i]`.
+    pub fn post(&self, f: &F, sub_types: &[&[bool]]) -> Vec<bool> {
+        let mut ty = vec![false; self.states.len()];
+        if let Some(clauses) = self.symbol_clauses.get(f) {
+            for (q, clause) in clauses.iter() {
+                ty[*q] = ty[*q] || clause.iter().any(|conjunction| {
+                    conjunction.iter().all(|(i, p)| *i < sub_types.len() && sub_types[*i][*p])
+                });
+            }
+        }
+        ty
+    }
+
+    /// Explore every reachable type, starting from the constants.
+    ///
+    /// For each symbol `f` and tuple of reachable types, `visit` is called once with `f`, the
+    /// tuple, the resulting type and `true` if the type has not been visited before.
+    /// The exploration stops as soon as `visit` returns `false`.
+    pub fn explore<V>(&mut self, mut visit: V) where V: FnMut(&Self, &'a F, &[usize], usize, bool) -> bool {
+        let symbols: Vec<&'a F> = self.symbols().collect();
+        explore_tuples(&symbols, |f| f.arity(), 0, |f, tuple| {
+            let sub_types: Vec<&[bool]> = tuple.iter().map(|i| self.types[*i].as_slice()).collect();
+            let ty = self.post(f, &sub_types);
+
+            let (index, new) = match self.known.get(&ty) {
+                Some(index) => (*index, false),
+                None => {
+                    let index = self.types.len();
+                    self.known.insert(ty.clone(), index);
+                    self.types.push(ty);
+                    (index, true)
+                }
+            };
+
+            if visit(self, f, tuple, index, new) {
+                Some(self.types.len())
+            } else {
+                None
+            }
+        })
+    }
+}
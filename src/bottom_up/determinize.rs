@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use crate::{Symbol, State, Label, NoLabel, StateSet, Ranked};
+use crate::utils::explore_tuples;
+use super::{Automaton, Configuration};
+
+type Transitions<'a, F, Q> = Vec<(&'a Configuration<F, Q>, &'a Q)>;
+
+/// Transitions of an automaton indexed by symbol and arity.
+pub(super) struct SignatureIndex<'a, F, Q: State> {
+    transitions: HashMap<(&'a F, usize), Transitions<'a, F, Q>>
+}
+
+impl<'a, F: Symbol, Q: State> SignatureIndex<'a, F, Q> {
+    pub fn new<L: Label>(aut: &'a Automaton<F, Q, L>) -> SignatureIndex<'a, F, Q> {
+        let mut transitions: HashMap<_, Vec<_>> = HashMap::new();
+        for (conf, _, q) in aut.transitions() {
+            transitions.entry(conf.signature()).or_default().push((conf, q))
+        }
+
+        SignatureIndex {
+            transitions
+        }
+    }
+
+    /// Return the transitions with the given symbol and arity.
+    pub fn transitions<'b>(&'b self, f: &'b F, arity: usize) -> &'b [(&'a Configuration<F, Q>, &'a Q)] {
+        match self.transitions.get(&(f, arity)) {
+            Some(transitions) => transitions,
+            None => &[]
+        }
+    }
+
+    /// Return the set of states reachable from the given symbol and sets of sub-states.
+    pub fn post(&self, f: &F, sets: &[&HashSet<&'a Q>]) -> HashSet<&'a Q> {
+        self.transitions(f, sets.len()).iter().filter(|(conf, _)| {
+            conf.states().iter().zip(sets.iter()).all(|(q, set)| set.contains(q))
+        }).map(|(_, q)| *q).collect()
+    }
+}
+
+impl<F: Symbol, Q: State + Ord, L: Label> Automaton<F, Q, L> {
+    /// Return a bottom-up deterministic automaton recognizing the same language.
+    ///
+    /// Each state of the new automaton is a set of states of `self`.
+    /// Only the sets reachable from the constants are created.
+    /// Labels are dropped, since a configuration may lead to the same state with different labels.
+    pub fn determinize(&self) -> Automaton<F, StateSet<Q>, NoLabel> {
+        let signature: Vec<(F, usize)> = self.signature().into_iter().collect();
+        self.subset_construction(&signature, false)
+    }
+
+    /// Return a deterministic and complete automaton recognizing the same language, over the
+    /// given alphabet.
+    ///
+    /// The empty set of states is used as a sink state, reached by every term over the alphabet
+    /// that is not recognized by any state of `self`.
+    pub fn determinize_complete<'a, A: IntoIterator<Item=&'a F>>(&self, alphabet: A) -> Automaton<F, StateSet<Q>, NoLabel> where F: 'a + Ranked {
+        let signature: Vec<(F, usize)> = alphabet.into_iter().map(|f| (f.clone(), f.arity())).collect();
+        self.subset_construction(&signature, true)
+    }
+
+    /// Return an automaton recognizing every term over the given alphabet that is not recognized
+    /// by `self`.
+    ///
+    /// The automaton is first determinized and completed over the alphabet, then its final and
+    /// non-final states are swapped.
+    pub fn complement<'a, A: IntoIterator<Item=&'a F>>(&self, alphabet: A) -> Automaton<F, StateSet<Q>, NoLabel> where F: 'a + Ranked {
+        let mut aut = self.determinize_complete(alphabet);
+        let states: Vec<StateSet<Q>> = aut.states().cloned().collect();
+        aut.final_states = states.into_iter().filter(|q| !aut.is_final(q)).collect();
+        aut
+    }
+
+    /// Subset construction over the given signature.
+    ///
+    /// If `complete` is true, the empty set is used as a sink state and every configuration
+    /// over the signature leads to a state, making the resulting automaton complete.
+    pub(crate) fn subset_construction(&self, signature: &[(F, usize)], complete: bool) -> Automaton<F, StateSet<Q>, NoLabel> {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.subset_construction(signature, complete)
+        }
+
+        let index = SignatureIndex::new(self);
+
+        let mut aut = Automaton::new();
+        let mut subsets: Vec<StateSet<Q>> = Vec::new();
+        let mut known: HashSet<StateSet<Q>> = HashSet::new();
+
+        if complete {
+            subsets.push(StateSet::new());
+            known.insert(StateSet::new());
+        }
+
+        explore_tuples(signature, |(_, arity)| *arity, subsets.len(), |(f, arity), indexes| {
+            let target: StateSet<Q> = index.transitions(f, *arity).iter().filter(|(conf, _)| {
+                conf.states().iter().zip(indexes.iter()).all(|(q, i)| subsets[*i].contains(q))
+            }).map(|(_, q)| (*q).clone()).collect();
+
+            if complete || !target.is_empty() {
+                if known.insert(target.clone()) {
+                    subsets.push(target.clone())
+                }
+
+                let states = indexes.iter().map(|i| subsets[*i].clone()).collect();
+                aut.add(Configuration(f.clone(), states), NoLabel, target);
+            }
+
+            Some(subsets.len())
+        });
+
+        for subset in subsets {
+            if subset.iter().any(|q| self.is_final(q)) {
+                aut.set_final(subset);
+            }
+        }
+
+        aut
+    }
+}
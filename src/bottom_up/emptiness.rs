@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use terms::Term;
+use crate::{Symbol, State, Label};
+use super::Automaton;
+
+impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
+    /// Compute, for each productive state, a smallest term (in number of symbols) recognized by
+    /// the state, along with its size.
+    ///
+    /// This is a least fixpoint over the transitions of the automaton: a state is productive if
+    /// it is the target of a transition whose sub-states are all productive, or of an epsilon
+    /// transition from a productive state.
+    /// Unproductive states do not appear in the returned map.
+    pub(crate) fn smallest_terms(&self) -> HashMap<Q, (usize, Term<F>)> {
+        let mut terms: HashMap<Q, (usize, Term<F>)> = HashMap::new();
+
+        loop {
+            let mut changed = false;
+
+            for (conf, _, q) in self.transitions() {
+                let mut size = 1;
+                let mut sub_terms = Vec::with_capacity(conf.len());
+                for sub_state in conf.states() {
+                    match terms.get(sub_state) {
+                        Some((sub_size, sub_term)) => {
+                            size += sub_size;
+                            sub_terms.push(sub_term.clone())
+                        },
+                        None => break
+                    }
+                }
+
+                if sub_terms.len() == conf.len() {
+                    let smaller = match terms.get(q) {
+                        Some((current_size, _)) => size < *current_size,
+                        None => true
+                    };
+
+                    if smaller {
+                        terms.insert(q.clone(), (size, Term::new(conf.symbol().clone(), sub_terms)));
+                        changed = true
+                    }
+                }
+            }
+
+            for (source, target) in self.epsilon_transitions() {
+                if let Some((size, term)) = terms.get(source) {
+                    let smaller = match terms.get(target) {
+                        Some((current_size, _)) => size < current_size,
+                        None => true
+                    };
+
+                    if smaller {
+                        let entry = (*size, term.clone());
+                        terms.insert(target.clone(), entry);
+                        changed = true
+                    }
+                }
+            }
+
+            if !changed {
+                break
+            }
+        }
+
+        terms
+    }
+
+    /// Return a smallest term recognized by the automaton, if any.
+    /// Return `None` if the language of the automaton is empty.
+    pub fn witness(&self) -> Option<Term<F>> {
+        let terms = self.smallest_terms();
+        let mut witness: Option<&(usize, Term<F>)> = None;
+        for q in self.final_states() {
+            if let Some(candidate) = terms.get(q) {
+                match witness {
+                    Some((size, _)) if *size <= candidate.0 => (),
+                    _ => witness = Some(candidate)
+                }
+            }
+        }
+
+        witness.map(|(_, term)| term.clone())
+    }
+
+    /// Checks if the language recognized by the automaton is empty.
+    /// Note that this is not the same as having no states: an automaton with states may still
+    /// recognize no term.
+    pub fn is_empty(&self) -> bool {
+        self.witness().is_none()
+    }
+}
@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use terms::Term;
+use crate::{Symbol, State, Label, Ranked};
+use crate::utils::combinations;
+use crate::inter::occurrences;
+use super::Automaton;
+use super::determinize::SignatureIndex;
+
+/// Sets of states of the right automaton explored for a given state of the left automaton, along
+/// with the term reaching them.
+type Antichain<'a, R, F> = Vec<(HashSet<&'a R>, Term<F>)>;
+
+impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
+    /// Checks if the language of `self` is included in the language of `other`.
+    /// Return a term recognized by `self` but not by `other` if it is not.
+    ///
+    /// This explores pairs `(q, S)` where `q` is a state of `self` and `S` is the set of states of
+    /// `other` reached by some term recognized by `q`, without determinizing `other`.
+    /// Since the states reachable from `S` are included in the states reachable from any
+    /// superset of `S`, a pair `(q, S')` with `S ⊆ S'` is never explored once `(q, S)` has been.
+    /// The explored pairs hence form an antichain.
+    pub fn is_included_in<R: State, M: Label>(&self, other: &Automaton<F, R, M>) -> Result<(), Term<F>> {
+        if !self.epsilon_transitions.is_empty() || !other.epsilon_transitions.is_empty() {
+            return self.without_epsilons().is_included_in(other.without_epsilons().as_ref())
+        }
+
+        let index = SignatureIndex::new(other);
+
+        let occurrences = occurrences(self);
+
+        let mut antichain: HashMap<&Q, Antichain<R, F>> = HashMap::new();
+        let mut pending = VecDeque::new();
+
+        for (conf, _, q) in self.transitions() {
+            if conf.len() == 0 {
+                pending.push_back((q, index.post(conf.symbol(), &[]), Term::new(conf.symbol().clone(), Vec::new())))
+            }
+        }
+
+        while let Some((q, set, term)) = pending.pop_front() {
+            let pairs = antichain.entry(q).or_default();
+            if pairs.iter().any(|(smaller, _)| smaller.is_subset(&set)) {
+                continue
+            }
+
+            if self.is_final(q) && !set.iter().any(|r| other.is_final(r)) {
+                return Err(term)
+            }
+
+            pairs.retain(|(larger, _)| !set.is_subset(larger));
+            pairs.push((set, term));
+            let new_pair = pairs.len() - 1;
+
+            if let Some(occurrences) = occurrences.get(q) {
+                for (i, conf, _, target) in occurrences {
+                    // Combine the new pair at position `i` with any explored pair at the other positions.
+                    let candidates: Vec<Vec<_>> = conf.states().iter().enumerate().map(|(j, sub_state)| {
+                        match antichain.get(sub_state) {
+                            Some(pairs) if *i == j => vec![&pairs[new_pair]],
+                            Some(pairs) => pairs.iter().collect(),
+                            None => Vec::new()
+                        }
+                    }).collect();
+
+                    for pairs in combinations(&candidates, |pairs| pairs.iter().cloned()) {
+                        let sets: Vec<&HashSet<&R>> = pairs.iter().map(|(set, _)| set).collect();
+                        let sub_terms = pairs.iter().map(|(_, term)| term.clone()).collect();
+                        pending.push_back((*target, index.post(conf.symbol(), &sets), Term::new(conf.symbol().clone(), sub_terms)))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks if `self` and `other` recognize the same language.
+    /// Return a term recognized by exactly one of them if they do not.
+    pub fn equivalent<R: State, M: Label>(&self, other: &Automaton<F, R, M>) -> Result<(), Term<F>> {
+        self.is_included_in(other)?;
+        other.is_included_in(self)
+    }
+
+    /// Checks if every term over the given alphabet is recognized by the automaton.
+    /// Return a term that is not recognized if it is not the case.
+    ///
+    /// This explores the sets of states reached by terms over the alphabet, as in the subset
+    /// construction, but only keeps the minimal sets (w.r.t. inclusion): if no final state is
+    /// reachable from a set, then no final state is reachable from any of its subsets.
+    pub fn is_universal<'a, A: IntoIterator<Item=&'a F>>(&self, alphabet: A) -> Result<(), Term<F>> where F: 'a + Ranked {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.is_universal(alphabet)
+        }
+
+        let index = SignatureIndex::new(self);
+        let alphabet: Vec<&F> = alphabet.into_iter().collect();
+
+        let mut antichain: Antichain<Q, F> = Vec::new();
+        let mut pending = VecDeque::new();
+
+        for f in alphabet.iter() {
+            if f.arity() == 0 {
+                pending.push_back((index.post(f, &[]), Term::new((*f).clone(), Vec::new())))
+            }
+        }
+
+        while let Some((set, term)) = pending.pop_front() {
+            if antichain.iter().any(|(smaller, _)| smaller.is_subset(&set)) {
+                continue
+            }
+
+            if !set.iter().any(|q| self.is_final(q)) {
+                return Err(term)
+            }
+
+            antichain.retain(|(larger, _)| !set.is_subset(larger));
+            antichain.push((set, term));
+            let new_pair = antichain.len() - 1;
+
+            for f in alphabet.iter() {
+                for i in 0..f.arity() {
+                    // Combine the new set at position `i` with any explored set at the other positions.
+                    let candidates: Vec<Vec<_>> = (0..f.arity()).map(|j| {
+                        if i == j {
+                            vec![&antichain[new_pair]]
+                        } else {
+                            antichain.iter().collect()
+                        }
+                    }).collect();
+
+                    for pairs in combinations(&candidates, |pairs| pairs.iter().cloned()) {
+                        let sets: Vec<&HashSet<&Q>> = pairs.iter().map(|(set, _)| set).collect();
+                        let sub_terms = pairs.iter().map(|(_, term)| term.clone()).collect();
+                        pending.push_back((index.post(f, &sets), Term::new((*f).clone(), sub_terms)))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
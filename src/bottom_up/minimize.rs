@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use crate::{Symbol, State, Label};
+use super::Automaton;
+
+impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
+    /// Minimize a deterministic and complete automaton.
+    ///
+    /// Return the minimal automaton along with the mapping from the states of `self` to the
+    /// states of the minimal automaton, where each state is mapped to the representative of its
+    /// equivalence class (see `map_states`).
+    ///
+    /// This uses partition refinement: starting from the final/non-final partition, two states
+    /// stay in the same class as long as replacing one by the other in any transition leads to
+    /// states of the same class.
+    /// If the automaton is not complete, missing transitions are considered to lead to a sink
+    /// state, and the result may not be minimal.
+    /// If it is not deterministic, the result may not recognize the same language.
+    pub fn minimize(&self) -> (Automaton<F, Q, L>, HashMap<Q, Q>) {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.minimize()
+        }
+
+        // States only appearing as sub-states must also be mapped.
+        let states = self.all_states();
+
+        // Index each context (symbol, position, other sub-states) in which a state appears,
+        // along with the target of the transition.
+        let mut contexts: HashMap<(&F, usize, Vec<&Q>), usize> = HashMap::new();
+        let mut state_contexts: HashMap<&Q, Vec<(usize, &Q)>> = HashMap::new();
+        for (conf, _, target) in self.transitions() {
+            for (i, q) in conf.states().iter().enumerate() {
+                let others = conf.states().iter().enumerate().filter(|(j, _)| *j != i).map(|(_, q)| q).collect();
+                let id = contexts.len();
+                let context = *contexts.entry((conf.symbol(), i, others)).or_insert(id);
+                state_contexts.entry(q).or_default().push((context, target));
+            }
+        }
+
+        let mut class: HashMap<&Q, usize> = states.iter().map(|q| (*q, if self.is_final(q) { 0 } else { 1 })).collect();
+        let mut class_count = 0;
+
+        loop {
+            let mut classes: HashMap<(usize, Vec<(usize, usize)>), usize> = HashMap::new();
+            let mut new_class = HashMap::new();
+            for q in states.iter() {
+                let mut signature: Vec<(usize, usize)> = match state_contexts.get(q) {
+                    Some(contexts) => contexts.iter().map(|(context, target)| (*context, class[target])).collect(),
+                    None => Vec::new()
+                };
+                signature.sort_unstable();
+                signature.dedup();
+
+                let id = classes.len();
+                let c = *classes.entry((class[q], signature)).or_insert(id);
+                new_class.insert(*q, c);
+            }
+
+            class = new_class;
+            if classes.len() == class_count {
+                break
+            } else {
+                class_count = classes.len()
+            }
+        }
+
+        let mut representatives: HashMap<usize, &Q> = HashMap::new();
+        let mut mapping = HashMap::new();
+        for q in states.iter() {
+            let representative = *representatives.entry(class[q]).or_insert(q);
+            mapping.insert((*q).clone(), representative.clone());
+        }
+
+        (self.map_states(|q| mapping[q].clone()), mapping)
+    }
+}
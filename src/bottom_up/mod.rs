@@ -1,6 +1,8 @@
 use std::fmt;
+use std::borrow::Cow;
 use std::collections::{hash_set, hash_map, HashSet, HashMap};
 use terms::{
+    Term,
     PatternLike,
     PatternLikeKind
 };
@@ -14,6 +16,8 @@ use crate::{
     NoLabel,
     Labeled,
     Language,
+    Pair,
+    intersection,
     //LanguageState,
     ConfigurationIterator
 };
@@ -21,9 +25,16 @@ use crate::{
 pub mod macros;
 pub mod search;
 pub mod width_search;
+mod emptiness;
+mod trim;
+mod determinize;
+mod minimize;
+mod inclusion;
+mod simulation;
 
 pub use search::*;
 pub use width_search::*;
+pub use simulation::Simulation;
 
 /// Tree automaton configuration.
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
@@ -127,6 +138,9 @@ pub struct Automaton<F, Q: State, L: Label> {
     // /// For each configuration, associate every known labeled version.
     // labeled_configurations: HashMap<Configuration<F, Q>, HashSet<Labeled<Configuration<F, Q>, L>>>,
 
+    /// For each state q, associates the set of states q' such that q -> q' is an epsilon transition.
+    epsilon_transitions: HashMap<Q, HashSet<Q>>,
+
     /// Final states of the automaton.
     final_states: HashSet<Q>
 }
@@ -134,7 +148,28 @@ pub struct Automaton<F, Q: State, L: Label> {
 pub type States<'a, F, Q, L> = hash_map::Keys<'a, Q, HashSet<Labeled<Configuration<F, Q>, L>>>;
 
 impl<F: Symbol, Q: State, L: Label> Language<F> for Automaton<F, Q, L> {
-    // ...
+    type Intersection = Automaton<F, Pair<Q, Q>, Pair<L, L>>;
+    type Union = Automaton<F, Indexed<Q>, L>;
+
+    fn accepts(&self, term: &Term<F>) -> bool {
+        Automaton::accepts(self, term)
+    }
+
+    fn witness(&self) -> Option<Term<F>> {
+        Automaton::witness(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Automaton::is_empty(self)
+    }
+
+    fn intersect(&self, other: &Self) -> Automaton<F, Pair<Q, Q>, Pair<L, L>> {
+        intersection(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Automaton<F, Indexed<Q>, L> {
+        Automaton::union(&[self, other])
+    }
 }
 
 impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
@@ -148,6 +183,7 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
             configuration_states: HashMap::new(),
             state_configurations: HashMap::new(),
             // labeled_configurations: HashMap::new(),
+            epsilon_transitions: HashMap::new(),
             final_states: HashSet::new()
         }
     }
@@ -173,6 +209,11 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
         self.final_states.insert(q)
     }
 
+    /// Checks if the given state is a final state.
+    pub fn is_final(&self, q: &Q) -> bool {
+        self.final_states.contains(q)
+    }
+
     /// Checks if the given state is in the automaton.
     /// Return true if at least one configuration is attached to the state in the automaton.
     pub fn includes(&self, q: &Q) -> bool {
@@ -252,6 +293,116 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
         }
     }
 
+    /// Remove a transition from the automaton.
+    /// Return `true` if the transition was in the automaton.
+    pub fn remove(&mut self, conf: &Configuration<F, Q>, label: &L, state: &Q) -> bool {
+        let labeled_state = (state.clone(), label.clone());
+        let removed = match self.configuration_states.get_mut(conf) {
+            Some(states) => {
+                let removed = states.remove(&labeled_state);
+                if states.is_empty() {
+                    self.configuration_states.remove(conf);
+                }
+                removed
+            },
+            None => false
+        };
+
+        if removed {
+            let labeled_conf = (conf.clone(), label.clone());
+            if let Some(configurations) = self.state_configurations.get_mut(state) {
+                configurations.remove(&labeled_conf);
+                if configurations.is_empty() {
+                    self.state_configurations.remove(state);
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// Add a new epsilon transition `source -> target` to the automaton.
+    /// Every term recognized by `source` is then also recognized by `target`.
+    ///
+    /// Return `true` if the transition was not already in the automaton.
+    pub fn add_epsilon(&mut self, source: Q, target: Q) -> bool {
+        if source == target {
+            false
+        } else {
+            self.epsilon_transitions.entry(source).or_default().insert(target)
+        }
+    }
+
+    /// Return an iterator over the epsilon transitions of the automaton.
+    pub fn epsilon_transitions(&self) -> impl Iterator<Item = (&Q, &Q)> {
+        self.epsilon_transitions.iter().flat_map(|(source, targets)| targets.iter().map(move |target| (source, target)))
+    }
+
+    /// Return the set of states reachable from the given state through epsilon transitions,
+    /// including the state itself.
+    pub fn epsilon_closure(&self, q: &Q) -> HashSet<Q> {
+        let mut closure = HashSet::new();
+        closure.insert(q.clone());
+        let mut pending = vec![q];
+
+        while let Some(q) = pending.pop() {
+            if let Some(targets) = self.epsilon_transitions.get(q) {
+                for target in targets {
+                    if closure.insert(target.clone()) {
+                        pending.push(target)
+                    }
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// Remove every epsilon transition, while preserving the language of each state.
+    /// Each transition `conf -> q` is copied to every state of the epsilon closure of `q`.
+    pub fn eliminate_epsilons(&mut self) {
+        let sources: Vec<Q> = self.epsilon_transitions.keys().cloned().collect();
+        for q in sources {
+            let confs: Vec<Labeled<Configuration<F, Q>, L>> = self.configurations_for_state(&q).cloned().collect();
+            for target in self.epsilon_closure(&q) {
+                if target != q {
+                    for (conf, label) in confs.iter() {
+                        self.add(conf.clone(), label.clone(), target.clone())
+                    }
+                }
+            }
+        }
+
+        self.epsilon_transitions.clear()
+    }
+
+    /// Every state of the automaton, including states only appearing as sub-states of
+    /// configurations, in epsilon transitions or as final states.
+    pub(crate) fn all_states(&self) -> HashSet<&Q> {
+        let mut states: HashSet<&Q> = self.states().collect();
+        for conf in self.configuration_states.keys() {
+            states.extend(conf.states());
+        }
+        for (source, target) in self.epsilon_transitions() {
+            states.insert(source);
+            states.insert(target);
+        }
+        states.extend(self.final_states());
+        states
+    }
+
+    /// Return the automaton itself if it has no epsilon transitions, or an equivalent copy of it
+    /// without epsilon transitions otherwise.
+    pub(crate) fn without_epsilons(&self) -> Cow<'_, Automaton<F, Q, L>> {
+        if self.epsilon_transitions.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            let mut aut = self.clone();
+            aut.eliminate_epsilons();
+            Cow::Owned(aut)
+        }
+    }
+
     /// Add new transitions in the automaton by adding and normalizing the given configuration,
     /// label and state.
     pub fn add_normalized<P: PatternLike<F, Q>, N>(&mut self, pattern: &P, normalizer: &mut N) -> Q
@@ -279,12 +430,38 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
                     state
                 }
             },
-            PatternLikeKind::Var(_) => {
-                panic!("epsilon transitions are not allowed!")
+            PatternLikeKind::Var(q) => {
+                // The pattern is already recognized by the state itself.
+                q.clone()
             }
         }
     }
 
+    /// Compute the run of the automaton on the given term.
+    /// Return the set of labeled states reachable at the root of the term, computed bottom-up.
+    pub fn run(&self, term: &Term<F>) -> HashSet<Labeled<Q, L>> {
+        let sub_states: Vec<HashSet<Q>> = term.sub_terms().iter().map(|sub| {
+            self.run(sub).into_iter().map(|(q, _)| q).collect()
+        }).collect();
+
+        let mut states = HashSet::new();
+        for sub_states in combinations(&sub_states, |states| states.iter().cloned()) {
+            let conf = Configuration(term.symbol().clone(), sub_states);
+            for (q, label) in self.states_for_configuration(&conf) {
+                for q in self.epsilon_closure(q) {
+                    states.insert((q, label.clone()));
+                }
+            }
+        }
+
+        states
+    }
+
+    /// Checks if the given term is recognized by the automaton.
+    pub fn accepts(&self, term: &Term<F>) -> bool {
+        self.run(term).iter().any(|(q, _)| self.is_final(q))
+    }
+
     // /// Find a run in the automaton that recognizes the given pattern.
     // pub fn find<X>(&self, pattern: Pattern<F, X>) -> Option<Configuration<F, Labeled<Q, L>>> {
     //     panic!("TODO")
@@ -319,15 +496,6 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
         Representatives::new(self)
     }
 
-    /// Complement the automaton.
-    /// This will invert the set of final and non-final states.
-    /// If the automaton is complete, then `self` becomes its own complement.
-    pub fn complement(&mut self) {
-        let states: HashSet<Q> = self.states().cloned().collect();
-        let new_final_states = states.difference(&self.final_states).cloned().collect();
-        self.final_states = new_final_states;
-    }
-
     /// Return the alphabet on which this automaton is defined.
     pub fn alphabet(&self) -> HashSet<F> {
         let mut alphabet = HashSet::new();
@@ -338,6 +506,17 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
         alphabet
     }
 
+    /// Return the signature of the automaton: every symbol appearing in a transition, along with
+    /// its arity.
+    pub fn signature(&self) -> HashSet<(F, usize)> {
+        let mut signature = HashSet::new();
+        for (conf, _, _) in self.transitions() {
+            signature.insert((conf.symbol().clone(), conf.len()));
+        }
+
+        signature
+    }
+
     pub fn map_states<R: State, M>(&self, g: M) -> Automaton<F, R, L> where M: Fn(&Q) -> R {
         let mut configuration_states: HashMap<Configuration<F, R>, HashSet<Labeled<R, L>>> = HashMap::new();
         for (conf, states) in self.configuration_states.iter() {
@@ -367,6 +546,11 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
             }
         }
 
+        let mut epsilon_transitions: HashMap<R, HashSet<R>> = HashMap::new();
+        for (q, targets) in self.epsilon_transitions.iter() {
+            epsilon_transitions.entry(g(q)).or_default().extend(targets.iter().map(|q| g(q)));
+        }
+
         let mut final_states = HashSet::new();
         for q in self.final_states.iter() {
             final_states.insert(g(q));
@@ -377,16 +561,46 @@ impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
             dummy_configurations: HashSet::new(),
             configuration_states: configuration_states,
             state_configurations: state_configurations,
+            epsilon_transitions: epsilon_transitions,
             final_states: final_states
         }
     }
+
+    /// Compute the union of the given automata.
+    ///
+    /// The states of each automaton are tagged with the index of the automaton in the given
+    /// slice, so that states with the same name in different automata are not merged.
+    /// The final states are the (tagged) final states of every automaton.
+    pub fn union(automata: &[&Automaton<F, Q, L>]) -> Automaton<F, Indexed<Q>, L> {
+        let mut aut = Automaton::new();
+
+        for (i, a) in automata.iter().enumerate() {
+            for (conf, label, q) in a.transitions() {
+                aut.add(conf.map(|q| Indexed(q.clone(), i)), label.clone(), Indexed(q.clone(), i));
+            }
+
+            for (source, target) in a.epsilon_transitions() {
+                aut.add_epsilon(Indexed(source.clone(), i), Indexed(target.clone(), i));
+            }
+
+            for q in a.final_states() {
+                aut.set_final(Indexed(q.clone(), i));
+            }
+        }
+
+        aut
+    }
 }
 
 impl<F: Symbol, Q: State> Automaton<F, Q, NoLabel> {
     /// Complete the language with the given automaton.
     /// Each state of `self` must be mappable into a state of `lang`, and each state of `lang`
     /// must be transformed into a dead state of `self`.
+    /// The epsilon transitions of `self` are eliminated first.
     pub fn complete_with<'a, A: Iterator<Item=&'a F>, R: State>(&mut self, alphabet: A, lang: &Automaton<F, R, NoLabel>) where F: 'a + Ranked, R: From<Q>, Q: From<R> {
+        self.eliminate_epsilons();
+        let lang = lang.without_epsilons();
+
         let mut states: Vec<Q> = self.states().map(|q| (*q).clone()).collect();
         states.extend(lang.states().map(|r| r.clone().into()));
 
@@ -447,6 +661,9 @@ impl<F: Symbol + fmt::Display, Q: State + fmt::Display, L: Label + fmt::Display>
         for (conf, label, q) in self.transitions() {
             write!(f, "{} -{}-> {}\n", conf, label, q)?;
         }
+        for (source, target) in self.epsilon_transitions() {
+            write!(f, "{} -> {}\n", source, target)?;
+        }
         write!(f, "final states: ")?;
         for q in self.final_states() {
             write!(f, "{} ", q)?;
@@ -4,6 +4,9 @@ use crate::{Symbol, State, Label, Labeled};
 use crate::utils::combinations;
 use super::{Automaton, Configuration, Configurations};
 
+/// Configurations of each automaton, with the same symbol.
+type CommonConfiguration<F, Q, L> = Vec<Labeled<Configuration<F, Q>, L>>;
+
 /// Common configurations searcher.
 pub struct CommonConfigurations<'a, F: Symbol, Q: State, L: Label> {
     automata: &'a [&'a Automaton<F, Q, L>],
@@ -11,6 +14,9 @@ pub struct CommonConfigurations<'a, F: Symbol, Q: State, L: Label> {
     iterators: Vec<Configurations<'a, F, Q, L>>,
     configurations: Vec<Labeled<Configuration<F, Q>, L>>,
     // pattern: P
+
+    /// Common configurations computed upfront, when some automaton has epsilon transitions.
+    eliminated: Option<std::vec::IntoIter<CommonConfiguration<F, Q, L>>>
 }
 
 impl<'a, F: Symbol, Q: State, L: Label> CommonConfigurations<'a, F, Q, L> {
@@ -21,6 +27,19 @@ impl<'a, F: Symbol, Q: State, L: Label> CommonConfigurations<'a, F, Q, L> {
     {
         // println!("looking for common confs: {:?} {:?}", positions, patterns);
 
+        if automata.iter().any(|aut| !aut.epsilon_transitions.is_empty()) {
+            let automata: Vec<_> = automata.iter().map(|aut| aut.without_epsilons()).collect();
+            let automata: Vec<&Automaton<F, Q, L>> = automata.iter().map(AsRef::as_ref).collect();
+            let configurations: Vec<_> = CommonConfigurations::new(&automata, positions).collect();
+            return CommonConfigurations {
+                automata: &[],
+                positions: &[],
+                iterators: Vec::new(),
+                configurations: Vec::new(),
+                eliminated: Some(configurations.into_iter())
+            }
+        }
+
         let mut iterators = Vec::with_capacity(positions.len());
         if !positions.is_empty() {
             iterators.push(automata[0].configurations_for_state(&positions[0]))
@@ -30,7 +49,8 @@ impl<'a, F: Symbol, Q: State, L: Label> CommonConfigurations<'a, F, Q, L> {
             automata: automata,
             positions: positions,
             iterators: iterators,
-            configurations: Vec::with_capacity(positions.len())
+            configurations: Vec::with_capacity(positions.len()),
+            eliminated: None
         }
     }
 }
@@ -39,6 +59,10 @@ impl<'a, F: Symbol, Q: State, L: Label> Iterator for CommonConfigurations<'a, F,
     type Item = Vec<Labeled<Configuration<F, Q>, L>>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(eliminated) = &mut self.eliminated {
+            return eliminated.next()
+        }
+
         if self.positions.is_empty() {
             None
         } else {
@@ -118,11 +142,26 @@ pub struct Representatives<'a, F: Symbol, Q: State, L: Label> {
     visited_transitions: Option<Rc<VisitedTransitions<'a, F, Q, L>>>,
     pending_states: Vec<&'a Q>,
     current_state: Option<(&'a Q, Configurations<'a, F, Q, L>)>,
-    current_configuration: Option<(&'a F, Box<dyn Iterator<Item=Vec<Term<F>>> + 'a>)>
+    current_configuration: Option<(&'a F, Box<dyn Iterator<Item=Vec<Term<F>>> + 'a>)>,
+
+    /// Representatives computed upfront, when the automaton has epsilon transitions.
+    eliminated: Option<std::vec::IntoIter<Term<F>>>
 }
 
 impl<'a, F: Symbol, Q: State, L: Label> Representatives<'a, F, Q, L> {
     pub fn new(aut: &'a Automaton<F, Q, L>) -> Representatives<'a, F, Q, L> {
+        if !aut.epsilon_transitions.is_empty() {
+            let representatives: Vec<_> = Representatives::new(aut.without_epsilons().as_ref()).collect();
+            return Representatives {
+                automaton: aut,
+                visited_transitions: None,
+                pending_states: Vec::new(),
+                current_state: None,
+                current_configuration: None,
+                eliminated: Some(representatives.into_iter())
+            }
+        }
+
         let mut pending_states: Vec<&'a Q> = aut.final_states.iter().collect();
         let current_state = match pending_states.pop() {
             Some(q) => Some((q, aut.configurations_for_state(q))),
@@ -133,7 +172,8 @@ impl<'a, F: Symbol, Q: State, L: Label> Representatives<'a, F, Q, L> {
             visited_transitions: None,
             pending_states: pending_states,
             current_state: current_state,
-            current_configuration: None
+            current_configuration: None,
+            eliminated: None
         }
     }
 
@@ -149,6 +189,10 @@ impl<'a, F: Symbol, Q: State, L: Label> Iterator for Representatives<'a, F, Q, L
     type Item = Term<F>;
 
     fn next(&mut self) -> Option<Term<F>> {
+        if let Some(eliminated) = &mut self.eliminated {
+            return eliminated.next()
+        }
+
         loop {
             match self.current_configuration {
                 Some((f, ref mut iterator)) => {
@@ -172,7 +216,8 @@ impl<'a, F: Symbol, Q: State, L: Label> Iterator for Representatives<'a, F, Q, L
                                             visited_transitions: visited_transitions.clone(),
                                             pending_states: Vec::new(),
                                             current_state: Some((q, aut.configurations_for_state(q))),
-                                            current_configuration: None
+                                            current_configuration: None,
+                                            eliminated: None
                                         });
                                         // println!("conf: {:?}", conf);
                                         self.current_configuration = Some((conf.symbol(), Box::new(sub_terms_it)));
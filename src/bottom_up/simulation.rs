@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use crate::{Symbol, State, Label};
+use crate::inter::occurrences;
+use super::Automaton;
+
+/// A preorder over the states of an automaton.
+/// It contains the pair `(p, q)` when `q` simulates `p`.
+pub type Simulation<Q> = HashSet<(Q, Q)>;
+
+impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
+    /// Compute the maximal downward simulation of the automaton.
+    ///
+    /// A state `q` simulates `p` downward if for every transition `f(p1, ..., pn) -> p` there is a
+    /// transition `f(q1, ..., qn) -> q` such that each `qi` simulates `pi`.
+    /// If `q` simulates `p` downward, then every term recognized by `p` is recognized by `q`.
+    pub fn downward_simulation(&self) -> Simulation<Q> {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.downward_simulation()
+        }
+
+        let states = self.all_states();
+        let mut simulation: HashSet<(&Q, &Q)> = HashSet::new();
+        for p in states.iter() {
+            for q in states.iter() {
+                simulation.insert((*p, *q));
+            }
+        }
+
+        loop {
+            let removed: Vec<(&Q, &Q)> = simulation.iter().filter(|(p, q)| {
+                !self.configurations_for_state(p).all(|(conf, _)| {
+                    self.configurations_for_state(q).any(|(other, _)| {
+                        conf.signature() == other.signature() && conf.states().iter().zip(other.states()).all(|(p, q)| simulation.contains(&(p, q)))
+                    })
+                })
+            }).cloned().collect();
+
+            if removed.is_empty() {
+                break
+            }
+
+            for pair in removed.iter() {
+                simulation.remove(pair);
+            }
+        }
+
+        simulation.into_iter().map(|(p, q)| (p.clone(), q.clone())).collect()
+    }
+
+    /// Compute the maximal upward simulation of the automaton induced by the given downward
+    /// simulation.
+    ///
+    /// A state `q` simulates `p` upward if `q` is final whenever `p` is, and for every transition
+    /// `f(p1, ..., p, ..., pn) -> p'` where `p` appears at position `i`, there is a transition
+    /// `f(q1, ..., q, ..., qn) -> q'` where `q` appears at position `i`, such that `q'` simulates
+    /// `p'` upward, and each other `qj` simulates `pj` w.r.t. the given downward simulation.
+    pub fn upward_simulation(&self, downward: &Simulation<Q>) -> Simulation<Q> {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.upward_simulation(downward)
+        }
+
+        let states = self.all_states();
+
+        let occurrences = occurrences(self);
+
+        let mut simulation: HashSet<(&Q, &Q)> = HashSet::new();
+        for p in states.iter() {
+            for q in states.iter() {
+                if !self.is_final(p) || self.is_final(q) {
+                    simulation.insert((*p, *q));
+                }
+            }
+        }
+
+        loop {
+            let removed: Vec<(&Q, &Q)> = simulation.iter().filter(|(p, q)| {
+                let p_occurrences = occurrences.get(p).map(Vec::as_slice).unwrap_or(&[]);
+                let q_occurrences = occurrences.get(q).map(Vec::as_slice).unwrap_or(&[]);
+                !p_occurrences.iter().all(|(i, conf, _, target)| {
+                    q_occurrences.iter().any(|(j, other, _, other_target)| {
+                        i == j && conf.signature() == other.signature() && simulation.contains(&(*target, *other_target)) && conf.states().iter().zip(other.states()).enumerate().all(|(k, (p, q))| {
+                            k == *i || downward.contains(&(p.clone(), q.clone()))
+                        })
+                    })
+                })
+            }).cloned().collect();
+
+            if removed.is_empty() {
+                break
+            }
+
+            for pair in removed.iter() {
+                simulation.remove(pair);
+            }
+        }
+
+        simulation.into_iter().map(|(p, q)| (p.clone(), q.clone())).collect()
+    }
+
+    /// Reduce the size of the automaton using its downward simulation, without determinizing it.
+    ///
+    /// States simulating each other (hence recognizing the same language) are merged, and each
+    /// transition `f(p1, ..., pn) -> q` is removed if there is another transition
+    /// `f(q1, ..., qn) -> q` with the same label where each `qi` simulates `pi`.
+    /// The recognized language is left unchanged.
+    ///
+    /// Return the reduced automaton along with the mapping from the states of `self` to the
+    /// states of the reduced automaton (see `map_states`).
+    pub fn reduce_by_simulation(&self) -> (Automaton<F, Q, L>, HashMap<Q, Q>) {
+        if let Cow::Owned(aut) = self.without_epsilons() {
+            return aut.reduce_by_simulation()
+        }
+
+        let simulation = self.downward_simulation();
+
+        let mut representatives: Vec<&Q> = Vec::new();
+        let mut mapping = HashMap::new();
+        for q in self.all_states() {
+            let representative = match representatives.iter().find(|r| simulation.contains(&(q.clone(), (**r).clone())) && simulation.contains(&((**r).clone(), q.clone()))) {
+                Some(r) => *r,
+                None => {
+                    representatives.push(q);
+                    q
+                }
+            };
+            mapping.insert(q.clone(), representative.clone());
+        }
+
+        let mut aut = self.map_states(|q| mapping[q].clone());
+
+        // Merging states does not change the language of the remaining states, hence the
+        // simulation of the quotient automaton is the quotient of the simulation.
+        let simulation: Simulation<Q> = simulation.into_iter().map(|(p, q)| (mapping[&p].clone(), mapping[&q].clone())).collect();
+
+        let mut subsumed = Vec::new();
+        for q in aut.states() {
+            for (conf, label) in aut.configurations_for_state(q) {
+                let is_subsumed = aut.configurations_for_state(q).any(|(other, other_label)| {
+                    label == other_label && conf != other && conf.signature() == other.signature() && conf.states().iter().zip(other.states()).all(|(p, q)| {
+                        simulation.contains(&(p.clone(), q.clone()))
+                    })
+                });
+
+                if is_subsumed {
+                    subsumed.push((conf.clone(), label.clone(), q.clone()));
+                }
+            }
+        }
+
+        for (conf, label, q) in subsumed.iter() {
+            aut.remove(conf, label, q);
+        }
+
+        (aut, mapping)
+    }
+}
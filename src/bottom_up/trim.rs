@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+use crate::{Symbol, State, Label};
+use super::Automaton;
+
+impl<F: Symbol, Q: State, L: Label> Automaton<F, Q, L> {
+    /// Return the set of productive states of the automaton.
+    /// A state is productive if it recognizes at least one term, possibly through epsilon
+    /// transitions.
+    pub fn productive_states(&self) -> HashSet<Q> {
+        let mut productive = HashSet::new();
+
+        loop {
+            let mut changed = false;
+
+            for (conf, _, q) in self.transitions() {
+                if !productive.contains(q) && conf.states().iter().all(|sub| productive.contains(sub)) {
+                    productive.insert(q.clone());
+                    changed = true
+                }
+            }
+
+            for (source, target) in self.epsilon_transitions() {
+                if productive.contains(source) && !productive.contains(target) {
+                    productive.insert(target.clone());
+                    changed = true
+                }
+            }
+
+            if !changed {
+                break
+            }
+        }
+
+        productive
+    }
+
+    /// Return the set of accessible states of the automaton.
+    /// A state is accessible if it is a final state, if it appears in a configuration leading
+    /// to an accessible state, or if it has an epsilon transition to an accessible state.
+    pub fn accessible_states(&self) -> HashSet<Q> {
+        let mut epsilon_sources: HashMap<&Q, Vec<&Q>> = HashMap::new();
+        for (source, target) in self.epsilon_transitions() {
+            epsilon_sources.entry(target).or_default().push(source)
+        }
+
+        let mut accessible: HashSet<Q> = self.final_states.clone();
+        let mut pending: Vec<Q> = accessible.iter().cloned().collect();
+
+        while let Some(q) = pending.pop() {
+            if let Some(sources) = epsilon_sources.get(&q) {
+                for source in sources {
+                    if accessible.insert((*source).clone()) {
+                        pending.push((*source).clone())
+                    }
+                }
+            }
+
+            for (conf, _) in self.configurations_for_state(&q) {
+                for sub in conf.states() {
+                    if accessible.insert(sub.clone()) {
+                        pending.push(sub.clone())
+                    }
+                }
+            }
+        }
+
+        accessible
+    }
+
+    /// Remove every state that is not productive or not accessible, along with every transition
+    /// involving such state.
+    /// The recognized language is left unchanged.
+    pub fn trim(&mut self) {
+        let productive = self.productive_states();
+        self.retain_states(|q| productive.contains(q));
+
+        // Accessibility must be computed after removing unproductive states, since a state may
+        // only be accessible through transitions involving unproductive states.
+        let accessible = self.accessible_states();
+        self.retain_states(|q| accessible.contains(q));
+    }
+
+    /// Only keep the states satisfying the given predicate, and remove every transition involving
+    /// any other state.
+    pub(crate) fn retain_states<P>(&mut self, keep: P) where P: Fn(&Q) -> bool {
+        self.configuration_states.retain(|conf, states| {
+            if conf.states().iter().all(&keep) {
+                states.retain(|(q, _)| keep(q));
+                !states.is_empty()
+            } else {
+                false
+            }
+        });
+
+        self.state_configurations.retain(|q, confs| {
+            if keep(q) {
+                confs.retain(|(conf, _)| conf.states().iter().all(&keep));
+                !confs.is_empty()
+            } else {
+                false
+            }
+        });
+
+        self.epsilon_transitions.retain(|q, targets| {
+            if keep(q) {
+                targets.retain(|q| keep(q));
+                !targets.is_empty()
+            } else {
+                false
+            }
+        });
+
+        self.final_states.retain(|q| keep(q));
+    }
+}
@@ -64,7 +64,7 @@ impl<'a, F: Symbol, Q: LanguageState<F, ()>> From<Q> for Automaton<F, Q, NoLabel
 	}
 }
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Indexed<Q: State>(pub Q, pub usize);
 
 impl<T: State + fmt::Display> fmt::Display for Indexed<T> {
@@ -1,15 +1,28 @@
 use std::fmt;
-use crate::{Language, Symbol, State, Label, Labeled};
+use std::collections::HashMap;
+use crate::{Language, Symbol, State, Label, Labeled, NoLabel, StateSet};
 use crate::bottom_up::{Automaton, Configuration, CommonConfigurations};
 use crate::combinations;
 
-trait Inter<F>: Language<F> {
+/// Intersection of languages.
+pub trait Inter<F>: Language<F> {
     type Output: Language<F>;
 
+    /// Compute the intersection of the given languages.
     fn inter(automata: &[&Self]) -> Self::Output;
 }
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// State (or label) of the intersection of two automata.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pair<A, B>(pub A, pub B);
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Pair<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}×{}", self.0, self.1)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Product<Q> {
     states: Vec<Q>
 }
@@ -29,6 +42,31 @@ impl<Q: fmt::Display> fmt::Display for Product<Q> {
     }
 }
 
+impl<Q> Product<Q> {
+    /// Return the states composing the product, one for each automaton.
+    pub fn states(&self) -> &[Q] {
+        &self.states
+    }
+
+    /// Return the number of states composing the product.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Return the state of the `i`-th automaton in the product.
+    pub fn get(&self, i: usize) -> Option<&Q> {
+        self.states.get(i)
+    }
+
+    pub fn into_inner(self) -> Vec<Q> {
+        self.states
+    }
+}
+
 impl<Q: Clone> Product<Q> {
     pub fn new(states: &[Q]) -> Product<Q> {
         Product {
@@ -36,7 +74,9 @@ impl<Q: Clone> Product<Q> {
         }
     }
 
-    fn configurations<'a, F: Symbol, L: Label>(&'a self, automata: &'a [&'a Automaton<F, Q, L>]) -> ProductConfigurations<'a, F, Q, L> where Q: State {
+    /// Return an iterator over the configurations of the product state, given the automata of
+    /// the product.
+    pub fn configurations<'a, F: Symbol, L: Label>(&'a self, automata: &'a [&'a Automaton<F, Q, L>]) -> ProductConfigurations<'a, F, Q, L> where Q: State {
         ProductConfigurations {
             it: Automaton::common_configurations(automata, &self.states)
         }
@@ -87,29 +127,190 @@ impl<'a, F: Symbol, Q: State, L: Label> Iterator for ProductConfigurations<'a, F
     }
 }
 
+/// For each state q of an automaton, the transitions in which q appears as a sub-state, along with
+/// its position in the configuration.
+pub(crate) type Occurrences<'a, F, Q, L> = HashMap<&'a Q, Vec<(usize, &'a Configuration<F, Q>, &'a L, &'a Q)>>;
+
+pub(crate) fn occurrences<'a, F: Symbol, Q: State, L: Label>(aut: &'a Automaton<F, Q, L>) -> Occurrences<'a, F, Q, L> {
+    let mut occurrences: Occurrences<F, Q, L> = HashMap::new();
+    for (conf, label, q) in aut.transitions() {
+        for (i, sub_state) in conf.states().iter().enumerate() {
+            occurrences.entry(sub_state).or_default().push((i, conf, label, q))
+        }
+    }
+
+    occurrences
+}
+
+/// Add a transition to a product automaton under construction.
+/// If the target state is new, it is added to the `pending` list.
+fn add_product_transition<F: Symbol, P: State, L: Label>(aut: &mut Automaton<F, P, L>, pending: &mut Vec<P>, conf: Configuration<F, P>, label: L, state: P) {
+    if !aut.includes(&state) {
+        pending.push(state.clone())
+    }
+
+    aut.add(conf, label, state)
+}
+
 impl<F: Symbol, Q: State, L: Label> Inter<F> for Automaton<F, Q, L> where Q: Clone {
     type Output = Automaton<F, Product<Q>, L>;
 
+    /// The product automaton is built bottom-up, starting from the constants.
+    /// Only product states recognizing at least one term are created.
+    /// The label of each product transition is the label of the first automaton.
+    /// The product of no automata is the empty automaton.
     fn inter(automata: &[&Self]) -> Automaton<F, Product<Q>, L> {
-        fn process_state<F: Symbol, Q: State, L: Label>(automata: &[&Automaton<F, Q, L>], aut: &mut Automaton<F, Product<Q>, L>, product_state: Product<Q>) where Q: Clone {
-            for (conf, label) in product_state.configurations(automata) {
-                aut.add(conf.clone(), label, product_state.clone());
+        if automata.is_empty() {
+            return Automaton::new()
+        }
+
+        let automata: Vec<_> = automata.iter().map(|a| a.without_epsilons()).collect();
+        let automata: Vec<&Self> = automata.iter().map(AsRef::as_ref).collect();
+
+        let mut aut = Automaton::new();
+        let mut pending = Vec::new();
+
+        let constants: Vec<Vec<_>> = automata.iter().map(|a| {
+            a.transitions().filter(|(conf, _, _)| conf.len() == 0).collect()
+        }).collect();
+        for transitions in combinations(&constants, |transitions| transitions.iter()) {
+            let (conf, label, _) = transitions[0];
+            if transitions.iter().all(|(other, _, _)| other.symbol() == conf.symbol()) {
+                let state = Product::from(transitions.iter().map(|(_, _, q)| *q).collect::<Vec<&Q>>());
+                add_product_transition(&mut aut, &mut pending, Configuration(conf.symbol().clone(), Vec::new()), (*label).clone(), state)
+            }
+        }
 
-                for sub_state in conf.states() {
-                    if !aut.includes(&sub_state) {
-                        process_state(automata, aut, sub_state.clone())
+        let occurrences: Vec<Occurrences<F, Q, L>> = automata.iter().map(|a| occurrences(a)).collect();
+        while let Some(product) = pending.pop() {
+            let arity = occurrences.iter().zip(product.states()).map(|(occurrences, q)| {
+                match occurrences.get(q) {
+                    Some(occurrences) => occurrences.iter().map(|(i, _, _, _)| i + 1).max().unwrap_or(0),
+                    None => 0
+                }
+            }).min().unwrap_or(0);
+
+            for i in 0..arity {
+                // For each automaton, the transitions where the product component appears at position `i`.
+                let candidates: Vec<Vec<_>> = occurrences.iter().zip(product.states()).map(|(occurrences, q)| {
+                    match occurrences.get(q) {
+                        Some(occurrences) => occurrences.iter().filter(|(j, _, _, _)| *j == i).map(|(_, conf, label, q)| (*conf, *label, *q)).collect(),
+                        None => Vec::new()
+                    }
+                }).collect();
+
+                for transitions in combinations(&candidates, |transitions| transitions.iter()) {
+                    let (conf, label, _) = transitions[0];
+                    if transitions.iter().all(|(other, _, _)| other.signature() == conf.signature()) {
+                        let states: Vec<Product<Q>> = (0..conf.len()).map(|j| {
+                            Product::from(transitions.iter().map(|(conf, _, _)| &conf.states()[j]).collect::<Vec<&Q>>())
+                        }).collect();
+
+                        if states.iter().all(|q| aut.includes(q)) {
+                            let state = Product::from(transitions.iter().map(|(_, _, q)| *q).collect::<Vec<&Q>>());
+                            add_product_transition(&mut aut, &mut pending, Configuration(conf.symbol().clone(), states), (*label).clone(), state)
+                        }
                     }
                 }
             }
         }
 
-        let mut aut = Automaton::new();
-
-        for final_states in combinations(automata, |a| a.final_states()) {
-            let product = final_states.into();
-            process_state(automata, &mut aut, product)
+        let finals: Vec<Product<Q>> = aut.states().filter(|product| {
+            automata.iter().zip(product.states()).all(|(a, q)| a.is_final(q))
+        }).cloned().collect();
+        for product in finals {
+            aut.set_final(product);
         }
 
         aut
     }
 }
+
+/// Compute the intersection of two automata, possibly with different types of states and labels.
+/// Each state (resp. label) of the resulting automaton is a pair of states (resp. labels) of the
+/// given automata.
+///
+/// The product automaton is built bottom-up, starting from the constants.
+/// Only product states recognizing at least one term are created.
+pub fn intersection<F: Symbol, Q1: State, L1: Label, Q2: State, L2: Label>(a: &Automaton<F, Q1, L1>, b: &Automaton<F, Q2, L2>) -> Automaton<F, Pair<Q1, Q2>, Pair<L1, L2>> {
+    product(a, b, |q1, q2| a.is_final(q1) && b.is_final(q2))
+}
+
+/// Compute the difference of two automata: the resulting automaton recognizes the terms
+/// recognized by `a` but not by `b`.
+///
+/// `b` is determinized and completed over the signature of `a`, so that each product state
+/// `(q, S)` is made of a state `q` of `a` and the set `S` of every state of `b` reached by the
+/// same terms.
+pub fn difference<F: Symbol, Q1: State, L1: Label, Q2: State + Ord, L2: Label>(a: &Automaton<F, Q1, L1>, b: &Automaton<F, Q2, L2>) -> Automaton<F, Pair<Q1, StateSet<Q2>>, Pair<L1, NoLabel>> {
+    let signature: Vec<(F, usize)> = a.signature().into_iter().collect();
+    let det_b = b.subset_construction(&signature, true);
+    product(a, &det_b, |q1, set| a.is_final(q1) && !set.iter().any(|q2| b.is_final(q2)))
+}
+
+/// Automaton recognizing the symmetric difference of two automata with states `Q1` and `Q2`.
+pub type SymmetricDifference<F, Q1, Q2> = Automaton<F, Pair<StateSet<Q1>, StateSet<Q2>>, Pair<NoLabel, NoLabel>>;
+
+/// Compute the symmetric difference of two automata: the resulting automaton recognizes the
+/// terms recognized by exactly one of `a` or `b`.
+///
+/// Both automata are determinized and completed over their common signature.
+pub fn symmetric_difference<F: Symbol, Q1: State + Ord, L1: Label, Q2: State + Ord, L2: Label>(a: &Automaton<F, Q1, L1>, b: &Automaton<F, Q2, L2>) -> SymmetricDifference<F, Q1, Q2> {
+    let mut signature = a.signature();
+    signature.extend(b.signature());
+    let signature: Vec<(F, usize)> = signature.into_iter().collect();
+
+    let det_a = a.subset_construction(&signature, true);
+    let det_b = b.subset_construction(&signature, true);
+    product(&det_a, &det_b, |set1, set2| {
+        set1.iter().any(|q1| a.is_final(q1)) != set2.iter().any(|q2| b.is_final(q2))
+    })
+}
+
+/// Compute the product of two automata, where a product state is final if it satisfies the
+/// given predicate.
+fn product<F: Symbol, Q1: State, L1: Label, Q2: State, L2: Label, P>(a: &Automaton<F, Q1, L1>, b: &Automaton<F, Q2, L2>, is_final: P) -> Automaton<F, Pair<Q1, Q2>, Pair<L1, L2>> where P: Fn(&Q1, &Q2) -> bool {
+    let a = a.without_epsilons();
+    let b = b.without_epsilons();
+
+    let mut aut = Automaton::new();
+    let mut pending = Vec::new();
+
+    for (conf1, label1, q1) in a.transitions() {
+        if conf1.len() == 0 {
+            for (q2, label2) in b.states_for_configuration(&Configuration(conf1.symbol().clone(), Vec::new())) {
+                let conf = Configuration(conf1.symbol().clone(), Vec::new());
+                add_product_transition(&mut aut, &mut pending, conf, Pair(label1.clone(), label2.clone()), Pair(q1.clone(), q2.clone()))
+            }
+        }
+    }
+
+    let a_occurrences = occurrences(&a);
+    let b_occurrences = occurrences(&b);
+    while let Some(Pair(q1, q2)) = pending.pop() {
+        if let (Some(occurrences1), Some(occurrences2)) = (a_occurrences.get(&q1), b_occurrences.get(&q2)) {
+            for (i, conf1, label1, target1) in occurrences1 {
+                for (j, conf2, label2, target2) in occurrences2 {
+                    if i == j && conf1.signature() == conf2.signature() {
+                        let states: Vec<Pair<Q1, Q2>> = conf1.states().iter().zip(conf2.states().iter()).map(|(q1, q2)| {
+                            Pair(q1.clone(), q2.clone())
+                        }).collect();
+
+                        if states.iter().all(|q| aut.includes(q)) {
+                            let conf = Configuration(conf1.symbol().clone(), states);
+                            let state = Pair((*target1).clone(), (*target2).clone());
+                            add_product_transition(&mut aut, &mut pending, conf, Pair((*label1).clone(), (*label2).clone()), state)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let finals: Vec<Pair<Q1, Q2>> = aut.states().filter(|Pair(q1, q2)| is_final(q1, q2)).cloned().collect();
+    for product in finals {
+        aut.set_final(product);
+    }
+
+    aut
+}
@@ -1,8 +1,34 @@
+use terms::Term;
 use crate::{Symbol, State};
 use crate::bottom_up::Configuration;
 
+/// Tree language.
+///
+/// This is implemented by every kind of automaton, so that code can be generic over the
+/// representation of the language.
 pub trait Language<F> {
-    // ...
+    /// Representation of the intersection of two languages.
+    type Intersection: Language<F>;
+
+    /// Representation of the union of two languages.
+    type Union: Language<F>;
+
+    /// Checks if the given term is in the language.
+    fn accepts(&self, term: &Term<F>) -> bool;
+
+    /// Return a term of the language, if any.
+    fn witness(&self) -> Option<Term<F>>;
+
+    /// Checks if the language is empty.
+    fn is_empty(&self) -> bool {
+        self.witness().is_none()
+    }
+
+    /// Compute the intersection of the two languages.
+    fn intersect(&self, other: &Self) -> Self::Intersection;
+
+    /// Compute the union of the two languages.
+    fn union(&self, other: &Self) -> Self::Union;
 }
 
 pub trait ConfigurationIterator<'a, F: Symbol, Q: State> : Iterator<Item = Configuration<F, Q>> {
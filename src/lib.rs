@@ -11,14 +11,18 @@ mod state;
 mod label;
 mod language;
 mod inter;
+mod transpose;
 mod utils;
 pub mod bottom_up;
 pub mod alternating;
+pub mod smt2;
+pub mod timbuk;
 
 pub use state::*;
 pub use label::*;
 pub use language::*;
 pub use inter::*;
+pub use transpose::*;
 
 pub use utils::*;
 
@@ -0,0 +1,115 @@
+use std::fmt::{self, Display, Write};
+use crate::{Symbol, State, Ranked};
+use crate::alternating::Automaton;
+use super::symbol;
+
+/// Write the given alternating automaton as a system of Constrained Horn Clauses in SMT-LIB2.
+///
+/// Terms are represented by the algebraic datatype `sort`, with one constructor per symbol of the
+/// automaton, so the automaton should contain at least one constant.
+/// Each state `q` is an uninterpreted predicate `q_q` over `sort`, defined by one clause per
+/// conjunction. The query asserts that no initial state recognizes any term, so the system is
+/// `sat` if and only if the language of the automaton is empty.
+///
+/// Symbols and states are written in the order of their names, so that the output is stable.
+pub fn write_chc<W: Write, F: Symbol + Ranked + Display, Q: State + Display>(out: &mut W, aut: &Automaton<F, Q, u32>, sort: &str) -> fmt::Result {
+    let sort = symbol(sort);
+
+    let mut symbols: Vec<(String, &F)> = Vec::new();
+    let mut states: Vec<(String, &Q)> = Vec::new();
+    for q in aut.states().chain(aut.initial_states()) {
+        states.push((symbol(&format!("q_{}", q)), q));
+        for (f, clause) in aut.clauses_for_state(q) {
+            symbols.push((symbol(&f.to_string()), f));
+            for conjunction in clause.iter() {
+                for (_, p) in conjunction.iter() {
+                    states.push((symbol(&format!("q_{}", p)), p));
+                }
+            }
+        }
+    }
+    symbols.sort_by(|(a, _), (b, _)| a.cmp(b));
+    symbols.dedup_by(|(a, _), (b, _)| a == b);
+    states.sort_by(|(a, _), (b, _)| a.cmp(b));
+    states.dedup_by(|(a, _), (b, _)| a == b);
+
+    writeln!(out, "(set-logic HORN)")?;
+
+    // Datatype.
+    write!(out, "(declare-datatypes (({} 0)) ((", sort)?;
+    for (i, (name, f)) in symbols.iter().enumerate() {
+        if i > 0 {
+            write!(out, " ")?;
+        }
+        write!(out, "({}", name)?;
+        for j in 0..f.arity() {
+            write!(out, " ({} {})", symbol(&format!("{}.{}", f, j)), sort)?;
+        }
+        write!(out, ")")?;
+    }
+    writeln!(out, ")))")?;
+
+    // Predicates.
+    for (name, _) in states.iter() {
+        writeln!(out, "(declare-fun {} ({}) Bool)", name, sort)?;
+    }
+
+    // Clauses.
+    for (name, q) in states.iter() {
+        let mut clauses: Vec<(String, &F, _)> = aut.clauses_for_state(q).map(|(f, clause)| {
+            (symbol(&f.to_string()), f, clause)
+        }).collect();
+        clauses.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        for (f_name, f, clause) in clauses {
+            let arity = f.arity();
+            let head = if arity == 0 {
+                format!("({} {})", name, f_name)
+            } else {
+                let vars: Vec<String> = (0..arity).map(|i| format!("x{}", i)).collect();
+                format!("({} ({} {}))", name, f_name, vars.join(" "))
+            };
+
+            for conjunction in clause.iter() {
+                // Out of range indexes are never satisfied.
+                if conjunction.iter().any(|(i, _)| *i as usize >= arity) {
+                    continue
+                }
+
+                let body: Vec<String> = conjunction.iter().map(|(i, p)| {
+                    format!("({} x{})", symbol(&format!("q_{}", p)), i)
+                }).collect();
+                let implication = match body.len() {
+                    0 => head.clone(),
+                    1 => format!("(=> {} {})", body[0], head),
+                    _ => format!("(=> (and {}) {})", body.join(" "), head)
+                };
+
+                if arity == 0 {
+                    writeln!(out, "(assert {})", implication)?;
+                } else {
+                    let vars: Vec<String> = (0..arity).map(|i| format!("(x{} {})", i, sort)).collect();
+                    writeln!(out, "(assert (forall ({}) {}))", vars.join(" "), implication)?;
+                }
+            }
+        }
+    }
+
+    // Query.
+    let mut initial_states: Vec<String> = aut.initial_states().map(|q| symbol(&format!("q_{}", q))).collect();
+    initial_states.sort();
+    for name in initial_states {
+        writeln!(out, "(assert (forall ((x {})) (=> ({} x) false)))", sort, name)?;
+    }
+
+    writeln!(out, "(check-sat)")
+}
+
+/// Return the given alternating automaton as a system of Constrained Horn Clauses in SMT-LIB2.
+///
+/// See [`write_chc`] for details.
+pub fn chc<F: Symbol + Ranked + Display, Q: State + Display>(aut: &Automaton<F, Q, u32>, sort: &str) -> String {
+    let mut out = String::new();
+    write_chc(&mut out, aut, sort).unwrap();
+    out
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use crate::{Rank, NoLabel};
+use crate::bottom_up::{Automaton, Configuration};
+use super::Error;
+use super::sexp::{self, SExp};
+
+/// Automata recognizing the terms of each sort, indexed by sort name.
+pub type Universes = HashMap<String, Automaton<Rank<String>, String, NoLabel>>;
+
+/// Constructor of a datatype, with the sort of each argument.
+type Constructor = (String, Vec<String>);
+
+fn invalid(exp: &SExp) -> Error {
+    Error::InvalidDeclaration(exp.to_string())
+}
+
+/// Parse the declaration of the constructors of a datatype.
+///
+/// Each constructor is either a list `(name (selector Sort) ...)`, or a single name.
+fn parse_constructors(constructors: &[SExp]) -> Result<Vec<Constructor>, Error> {
+    constructors.iter().map(|constructor| {
+        match constructor {
+            SExp::Atom(name) => Ok((name.clone(), Vec::new())),
+            SExp::List(items) => {
+                let name = items.first().and_then(SExp::as_atom).ok_or_else(|| invalid(constructor))?;
+                let sorts = items[1..].iter().map(|selector| {
+                    match selector.as_list() {
+                        Some([SExp::Atom(_), SExp::Atom(sort)]) => Ok(sort.clone()),
+                        Some([SExp::Atom(_), sort @ SExp::List(_)]) => Err(Error::Parametric(sort.to_string())),
+                        _ => Err(invalid(selector))
+                    }
+                }).collect::<Result<Vec<_>, _>>()?;
+                Ok((name.to_string(), sorts))
+            }
+        }
+    }).collect()
+}
+
+/// Parse a `declare-datatypes` or `declare-datatype` command.
+///
+/// Both the SMT-LIB 2.6 syntax `(declare-datatypes ((Sort 0) ...) ((constructor ...) ...))` and
+/// the legacy syntax `(declare-datatypes () ((Sort constructor ...) ...))` are accepted.
+fn parse_declaration(command: &SExp, datatypes: &mut Vec<(String, Vec<Constructor>)>) -> Result<(), Error> {
+    let items = match command.as_list() {
+        Some(items) => items,
+        None => return Ok(())
+    };
+
+    match items.first().and_then(SExp::as_atom) {
+        Some("declare-datatype") => {
+            match &items[1..] {
+                [SExp::Atom(sort), SExp::List(constructors)] => {
+                    if constructors.first().and_then(SExp::as_atom) == Some("par") {
+                        return Err(Error::Parametric(sort.clone()))
+                    }
+                    datatypes.push((sort.clone(), parse_constructors(constructors)?))
+                },
+                _ => return Err(invalid(command))
+            }
+        },
+        Some("declare-datatypes") => {
+            match &items[1..] {
+                [SExp::List(params), SExp::List(declarations)] if params.is_empty() => {
+                    // Legacy syntax.
+                    for declaration in declarations {
+                        match declaration.as_list() {
+                            Some([SExp::Atom(sort), constructors @ ..]) => {
+                                datatypes.push((sort.clone(), parse_constructors(constructors)?))
+                            },
+                            _ => return Err(invalid(declaration))
+                        }
+                    }
+                },
+                [SExp::List(sorts), SExp::List(declarations)] if sorts.len() == declarations.len() => {
+                    for (sort, declaration) in sorts.iter().zip(declarations) {
+                        let sort = match sort.as_list() {
+                            Some([SExp::Atom(sort), SExp::Atom(arity)]) => {
+                                if arity != "0" {
+                                    return Err(Error::Parametric(sort.clone()))
+                                }
+                                sort
+                            },
+                            _ => return Err(invalid(sort))
+                        };
+
+                        match declaration.as_list() {
+                            Some(constructors) => {
+                                if constructors.first().and_then(SExp::as_atom) == Some("par") {
+                                    return Err(Error::Parametric(sort.clone()))
+                                }
+                                datatypes.push((sort.clone(), parse_constructors(constructors)?))
+                            },
+                            None => return Err(invalid(declaration))
+                        }
+                    }
+                },
+                _ => return Err(invalid(command))
+            }
+        },
+        _ => ()
+    }
+
+    Ok(())
+}
+
+/// Parse the datatype declarations of an SMT-LIB2 script.
+///
+/// For each declared sort, this returns an automaton recognizing every term of that sort, whose
+/// states are the sort names and whose symbols are the constructors, ranked by their number of
+/// selectors. Commands other than `declare-datatypes` and `declare-datatype` are ignored.
+/// Parametric datatypes, and constructors using sorts that are not declared datatypes (such as
+/// `Int`), are not supported.
+pub fn parse_datatypes(input: &str) -> Result<Universes, Error> {
+    let mut datatypes = Vec::new();
+    for command in sexp::parse(input)?.iter() {
+        parse_declaration(command, &mut datatypes)?;
+    }
+
+    let mut universe = Automaton::new();
+    for (sort, constructors) in datatypes.iter() {
+        for (name, sorts) in constructors.iter() {
+            if let Some(undefined) = sorts.iter().find(|s| !datatypes.iter().any(|(sort, _)| sort == *s)) {
+                return Err(Error::UndefinedSort(undefined.clone()))
+            }
+
+            let f = Rank(name.clone(), sorts.len());
+            universe.add(Configuration(f, sorts.clone()), NoLabel, sort.clone());
+        }
+    }
+
+    Ok(datatypes.into_iter().map(|(sort, _)| {
+        let mut aut = universe.clone();
+        aut.set_final(sort.clone());
+        aut.trim();
+        (sort, aut)
+    }).collect())
+}
@@ -0,0 +1,65 @@
+//! Conversion of automata from and to SMT-LIB2.
+
+use std::fmt;
+
+mod sexp;
+mod chc;
+mod datatypes;
+
+pub use chc::*;
+pub use datatypes::*;
+
+/// SMT-LIB2 parsing error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input ends in the middle of an expression.
+    UnexpectedEnd,
+
+    /// Unmatched closing parenthesis, at the given byte offset.
+    UnexpectedClose(usize),
+
+    /// Ill-formed datatype declaration.
+    InvalidDeclaration(String),
+
+    /// Parametric datatypes are not supported.
+    Parametric(String),
+
+    /// The given sort is not a declared datatype.
+    UndefinedSort(String)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Error::UnexpectedClose(offset) => write!(f, "unexpected `)` at offset {}", offset),
+            Error::InvalidDeclaration(exp) => write!(f, "invalid datatype declaration `{}`", exp),
+            Error::Parametric(sort) => write!(f, "parametric datatype `{}` is not supported", sort),
+            Error::UndefinedSort(sort) => write!(f, "undefined datatype `{}`", sort)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Characters allowed in SMT-LIB2 simple symbols, in addition to letters and digits.
+const SYMBOL_CHARS: &str = "~!@$%^&*_-+=<>.?/";
+
+/// Format the given name as an SMT-LIB2 symbol.
+///
+/// The name is used as is if it is a simple symbol, and quoted otherwise.
+/// Since quoted symbols cannot contain `|` and `\`, these are replaced by `_`.
+pub fn symbol(name: &str) -> String {
+    let simple = match name.chars().next() {
+        Some(c) if !c.is_ascii_digit() => {
+            name.chars().all(|c| c.is_ascii_alphanumeric() || SYMBOL_CHARS.contains(c))
+        },
+        _ => false
+    };
+
+    if simple {
+        name.to_string()
+    } else {
+        format!("|{}|", name.replace(['|', '\\'], "_"))
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use super::Error;
+
+/// SMT-LIB2 S-expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SExp {
+    /// Symbol, keyword or literal.
+    ///
+    /// Quoted symbols are unquoted.
+    Atom(String),
+
+    /// Parenthesized list of expressions.
+    List(Vec<SExp>)
+}
+
+impl SExp {
+    pub fn as_atom(&self) -> Option<&str> {
+        match self {
+            SExp::Atom(name) => Some(name),
+            SExp::List(_) => None
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[SExp]> {
+        match self {
+            SExp::Atom(_) => None,
+            SExp::List(items) => Some(items)
+        }
+    }
+}
+
+impl fmt::Display for SExp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SExp::Atom(name) => write!(f, "{}", name),
+            SExp::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Parse a sequence of S-expressions.
+pub fn parse(input: &str) -> Result<Vec<SExp>, Error> {
+    let mut stack: Vec<Vec<SExp>> = vec![Vec::new()];
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '(' => stack.push(Vec::new()),
+            ')' => {
+                if stack.len() == 1 {
+                    return Err(Error::UnexpectedClose(offset))
+                }
+
+                let list = stack.pop().unwrap();
+                stack.last_mut().unwrap().push(SExp::List(list))
+            },
+            ';' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break
+                    }
+                }
+            },
+            '|' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '|')) => break,
+                        Some((_, c)) => name.push(c),
+                        None => return Err(Error::UnexpectedEnd)
+                    }
+                }
+                stack.last_mut().unwrap().push(SExp::Atom(name))
+            },
+            '"' => {
+                let mut literal = String::from("\"");
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => {
+                            literal.push('"');
+                            // `""` is an escaped quote.
+                            match chars.peek() {
+                                Some((_, '"')) => {
+                                    chars.next();
+                                },
+                                _ => break
+                            }
+                        },
+                        Some((_, c)) => literal.push(c),
+                        None => return Err(Error::UnexpectedEnd)
+                    }
+                }
+                stack.last_mut().unwrap().push(SExp::Atom(literal))
+            },
+            c if c.is_whitespace() => (),
+            c => {
+                let mut name = c.to_string();
+                while let Some((_, c)) = chars.peek() {
+                    if c.is_whitespace() || "()|\";".contains(*c) {
+                        break
+                    }
+                    name.push(*c);
+                    chars.next();
+                }
+                stack.last_mut().unwrap().push(SExp::Atom(name))
+            }
+        }
+    }
+
+    if stack.len() > 1 {
+        return Err(Error::UnexpectedEnd)
+    }
+
+    Ok(stack.pop().unwrap())
+}
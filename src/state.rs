@@ -1,6 +1,8 @@
 use std::hash::Hash;
-#[cfg(debug_assertions)]
 use std::fmt;
+use std::ops::Deref;
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
 
 #[cfg(not(debug_assertions))]
 pub trait State = Hash + Clone + Eq;
@@ -23,3 +25,60 @@ pub trait State = Hash + Clone + Eq + fmt::Display + fmt::Debug;
 // impl State for i64 {}
 //
 // impl<A: Hash + Clone + Eq, B: Hash + Clone + Eq> State for (A, B) {}
+
+/// A set of states, used as the state of automata built by subset construction.
+///
+/// Sets are ordered so that they can be hashed and compared regardless of their insertion order.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StateSet<Q: Ord>(pub BTreeSet<Q>);
+
+impl<Q: Ord> StateSet<Q> {
+    /// Create a new empty set of states.
+    pub fn new() -> StateSet<Q> {
+        StateSet(BTreeSet::new())
+    }
+
+    pub fn into_inner(self) -> BTreeSet<Q> {
+        self.0
+    }
+}
+
+impl<Q: Ord> Default for StateSet<Q> {
+    fn default() -> StateSet<Q> {
+        StateSet::new()
+    }
+}
+
+impl<Q: Ord> Deref for StateSet<Q> {
+    type Target = BTreeSet<Q>;
+
+    fn deref(&self) -> &BTreeSet<Q> {
+        &self.0
+    }
+}
+
+impl<Q: Ord> From<BTreeSet<Q>> for StateSet<Q> {
+    fn from(set: BTreeSet<Q>) -> StateSet<Q> {
+        StateSet(set)
+    }
+}
+
+impl<Q: Ord> FromIterator<Q> for StateSet<Q> {
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> StateSet<Q> {
+        StateSet(iter.into_iter().collect())
+    }
+}
+
+impl<Q: Ord + fmt::Display> fmt::Display for StateSet<Q> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        let mut it = self.0.iter();
+        if let Some(head) = it.next() {
+            head.fmt(f)?;
+            for q in it {
+                write!(f, ", {}", q)?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
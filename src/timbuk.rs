@@ -0,0 +1,274 @@
+//! Reader and writer for the Timbuk tree automata format.
+//!
+//! A Timbuk file looks like this:
+//! ```text
+//! Ops nil:0 cons:2 a:0
+//! Automaton lists
+//! States q0 q1
+//! Final States q1
+//! Transitions
+//! a -> q0
+//! nil -> q1
+//! cons(q0, q1) -> q1
+//! ```
+//! A transition `q -> p` between two states is an epsilon transition.
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
+use crate::{Rank, NoLabel};
+use crate::bottom_up::{Automaton, Configuration};
+
+/// Timbuk parsing error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input ends before the end of the automaton.
+    UnexpectedEnd,
+
+    /// Unexpected token, at the given line.
+    Unexpected(String, usize),
+
+    /// Invalid symbol declaration, at the given line.
+    InvalidSymbol(String, usize),
+
+    /// The given symbol, used at the given line, is not declared in `Ops`.
+    UndeclaredSymbol(String, usize),
+
+    /// The given state, used at the given line, is not declared in `States`.
+    UndeclaredState(String, usize),
+
+    /// The given symbol is used with the wrong number of arguments at the given line.
+    InvalidArity(String, usize)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Error::Unexpected(token, line) => write!(f, "line {}: unexpected `{}`", line, token),
+            Error::InvalidSymbol(token, line) => write!(f, "line {}: invalid symbol declaration `{}`", line, token),
+            Error::UndeclaredSymbol(name, line) => write!(f, "line {}: undeclared symbol `{}`", line, name),
+            Error::UndeclaredState(name, line) => write!(f, "line {}: undeclared state `{}`", line, name),
+            Error::InvalidArity(name, line) => write!(f, "line {}: wrong number of arguments for `{}`", line, name)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A token with its line number.
+type Token<'a> = (&'a str, usize);
+
+/// Split the input into names, `(`, `)`, `,` and `->`.
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut rest = line;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break
+            }
+
+            let len = if rest.starts_with("->") {
+                2
+            } else if rest.starts_with(['(', ')', ',']) {
+                1
+            } else {
+                // A name ends before a space, a delimiter or an arrow.
+                let end = rest.find(|c: char| c.is_whitespace() || "(),".contains(c)).unwrap_or(rest.len());
+                rest[..end].find("->").unwrap_or(end)
+            };
+
+            tokens.push((&rest[..len], i + 1));
+            rest = &rest[len..];
+        }
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token<'a>>>
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Result<Token<'a>, Error> {
+        self.tokens.next().ok_or(Error::UnexpectedEnd)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<usize, Error> {
+        let (token, line) = self.next()?;
+        if token == expected {
+            Ok(line)
+        } else {
+            Err(Error::Unexpected(token.to_string(), line))
+        }
+    }
+
+    /// Return the names until the given keyword, excluded.
+    fn names_until(&mut self, keyword: &str) -> Result<Vec<Token<'a>>, Error> {
+        let mut names = Vec::new();
+        loop {
+            match self.tokens.peek() {
+                Some((token, _)) if *token == keyword => return Ok(names),
+                Some((token, line)) if ["(", ")", ",", "->"].contains(token) => {
+                    return Err(Error::Unexpected(token.to_string(), *line))
+                },
+                Some(_) => names.push(self.next()?),
+                None => return Err(Error::UnexpectedEnd)
+            }
+        }
+    }
+}
+
+/// Parse an automaton in the Timbuk format.
+///
+/// The name of the automaton is ignored, and so are the declared symbols that are not used by
+/// any transition. States may be declared with their arity (always `0`) as in `q:0`.
+pub fn parse(input: &str) -> Result<Automaton<Rank<String>, String, NoLabel>, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(input).into_iter().peekable()
+    };
+
+    parser.expect("Ops")?;
+    let mut symbols = HashMap::new();
+    for (op, line) in parser.names_until("Automaton")? {
+        match op.rsplit_once(':').map(|(name, arity)| (name, arity.parse::<usize>())) {
+            Some((name, Ok(arity))) if !name.is_empty() => {
+                symbols.insert(name, arity);
+            },
+            _ => return Err(Error::InvalidSymbol(op.to_string(), line))
+        }
+    }
+
+    parser.expect("Automaton")?;
+    parser.next()?;
+
+    parser.expect("States")?;
+    let mut states = HashSet::new();
+    for (state, line) in parser.names_until("Final")? {
+        match state.rsplit_once(':') {
+            Some((name, "0")) => states.insert(name),
+            Some(_) => return Err(Error::Unexpected(state.to_string(), line)),
+            None => states.insert(state)
+        };
+    }
+
+    let state = |(name, line): Token| {
+        if states.contains(name) {
+            Ok(name.to_string())
+        } else {
+            Err(Error::UndeclaredState(name.to_string(), line))
+        }
+    };
+
+    let mut aut = Automaton::new();
+
+    parser.expect("Final")?;
+    parser.expect("States")?;
+    for q in parser.names_until("Transitions")? {
+        aut.set_final(state(q)?);
+    }
+
+    parser.expect("Transitions")?;
+    while let Some((name, line)) = parser.tokens.next() {
+        let mut sub_states = Vec::new();
+        if parser.tokens.peek().map(|(token, _)| *token) == Some("(") {
+            parser.next()?;
+            if parser.tokens.peek().map(|(token, _)| *token) == Some(")") {
+                parser.next()?;
+            } else {
+                loop {
+                    sub_states.push(state(parser.next()?)?);
+                    match parser.next()? {
+                        (",", _) => (),
+                        (")", _) => break,
+                        (token, line) => return Err(Error::Unexpected(token.to_string(), line))
+                    }
+                }
+            }
+        }
+
+        parser.expect("->")?;
+        let target = state(parser.next()?)?;
+
+        match symbols.get(name) {
+            Some(arity) if *arity == sub_states.len() => {
+                let f = Rank(name.to_string(), *arity);
+                aut.add(Configuration(f, sub_states), NoLabel, target);
+            },
+            Some(_) => return Err(Error::InvalidArity(name.to_string(), line)),
+            None if sub_states.is_empty() && states.contains(name) => {
+                aut.add_epsilon(name.to_string(), target);
+            },
+            None => return Err(Error::UndeclaredSymbol(name.to_string(), line))
+        }
+    }
+
+    Ok(aut)
+}
+
+/// Write the given automaton in the Timbuk format, with the given name.
+///
+/// Only the symbols used by the transitions are declared.
+/// Everything is written in lexicographic order, so that the output is stable.
+pub fn write<W: Write>(out: &mut W, aut: &Automaton<Rank<String>, String, NoLabel>, name: &str) -> fmt::Result {
+    let mut symbols: Vec<(&str, usize)> = Vec::new();
+    let mut states: Vec<&String> = aut.final_states().collect();
+    let mut transitions: Vec<String> = Vec::new();
+
+    for (Configuration(Rank(f, arity), sub_states), _, q) in aut.transitions() {
+        symbols.push((f, *arity));
+        states.push(q);
+        states.extend(sub_states.iter());
+        if sub_states.is_empty() {
+            transitions.push(format!("{} -> {}", f, q))
+        } else {
+            let sub_states: Vec<&str> = sub_states.iter().map(String::as_str).collect();
+            transitions.push(format!("{}({}) -> {}", f, sub_states.join(","), q))
+        }
+    }
+
+    for (source, target) in aut.epsilon_transitions() {
+        states.push(source);
+        states.push(target);
+        transitions.push(format!("{} -> {}", source, target))
+    }
+
+    symbols.sort();
+    symbols.dedup();
+    states.sort();
+    states.dedup();
+    transitions.sort();
+    let mut final_states: Vec<&String> = aut.final_states().collect();
+    final_states.sort();
+
+    write!(out, "Ops")?;
+    for (f, arity) in symbols {
+        write!(out, " {}:{}", f, arity)?;
+    }
+
+    write!(out, "\nAutomaton {}\nStates", name)?;
+    for q in states {
+        write!(out, " {}", q)?;
+    }
+
+    write!(out, "\nFinal States")?;
+    for q in final_states {
+        write!(out, " {}", q)?;
+    }
+
+    writeln!(out, "\nTransitions")?;
+    for transition in transitions {
+        writeln!(out, "{}", transition)?;
+    }
+
+    Ok(())
+}
+
+/// Return the given automaton in the Timbuk format, with the given name.
+///
+/// See [`write`] for details.
+pub fn to_string(aut: &Automaton<Rank<String>, String, NoLabel>, name: &str) -> String {
+    let mut out = String::new();
+    write(&mut out, aut, name).unwrap();
+    out
+}
@@ -1,10 +1,84 @@
-trait Transpose<P> {
+use std::collections::HashMap;
+use crate::{Symbol, State, Label};
+use crate::bottom_up::{Automaton, Configuration};
+
+pub trait Transpose<P> {
     /// Return the transposed version of the language.
     fn transpose(&self, pattern: P) -> Self;
 }
 
-impl<F, Q, L, P> Transpose<P> for Automaton<F, Q, L> {
-    fn transpose(&self, pattern: P) -> Automaton<F, Q, L> {
-        // ...
+/// Permutations of the sub-terms of some symbols.
+///
+/// For a symbol `f` associated to the permutation `p`, each term `f(t0, ..., tn)` is
+/// transposed into `f(t[p[0]], ..., t[p[n]])`.
+/// Terms whose symbol has no associated permutation are left unchanged.
+#[derive(Clone, Debug)]
+pub struct Permutations<F: Symbol> {
+    permutations: HashMap<F, Vec<usize>>
+}
+
+impl<F: Symbol> Permutations<F> {
+    /// Create a new empty set of permutations, leaving every term unchanged.
+    pub fn new() -> Permutations<F> {
+        Permutations {
+            permutations: HashMap::new()
+        }
+    }
+
+    /// Set the permutation associated to the given symbol.
+    /// The permutation must contain each position between `0` and its length exactly once.
+    /// Return the permutation back as an error if it is not the case.
+    pub fn set(&mut self, f: F, permutation: Vec<usize>) -> Result<(), Vec<usize>> {
+        let mut positions = permutation.clone();
+        positions.sort_unstable();
+        if positions.iter().enumerate().all(|(i, j)| i == *j) {
+            self.permutations.insert(f, permutation);
+            Ok(())
+        } else {
+            Err(permutation)
+        }
+    }
+
+    /// Return the permutation associated to the given symbol, if any.
+    pub fn get(&self, f: &F) -> Option<&[usize]> {
+        self.permutations.get(f).map(Vec::as_slice)
+    }
+
+    /// Permute the given configuration.
+    /// Configurations whose arity does not match the length of the permutation are unchanged.
+    pub fn apply<Q: State>(&self, conf: &Configuration<F, Q>) -> Configuration<F, Q> {
+        match self.get(conf.symbol()) {
+            Some(permutation) if permutation.len() == conf.len() => {
+                let states = permutation.iter().map(|i| conf.states()[*i].clone()).collect();
+                Configuration(conf.symbol().clone(), states)
+            },
+            _ => conf.clone()
+        }
+    }
+}
+
+impl<F: Symbol> Default for Permutations<F> {
+    fn default() -> Permutations<F> {
+        Permutations::new()
+    }
+}
+
+impl<'a, F: Symbol, Q: State, L: Label> Transpose<&'a Permutations<F>> for Automaton<F, Q, L> {
+    fn transpose(&self, permutations: &'a Permutations<F>) -> Automaton<F, Q, L> {
+        let mut aut = Automaton::new();
+
+        for (conf, label, q) in self.transitions() {
+            aut.add(permutations.apply(conf), label.clone(), q.clone());
+        }
+
+        for (source, target) in self.epsilon_transitions() {
+            aut.add_epsilon(source.clone(), target.clone());
+        }
+
+        for q in self.final_states() {
+            aut.set_final(q.clone());
+        }
+
+        aut
     }
 }
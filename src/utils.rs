@@ -71,6 +71,46 @@ pub fn combinations<'a, T, F, I, E: Clone>(sources: &'a [T], f: F) -> Combinatio
     Combinations::new(sources, f)
 }
 
+/// Explore the tuples of indexes of known elements, for each of the given items, while new
+/// elements are discovered.
+///
+/// Each tuple of length `arity(item)` is visited once, when its greatest index is processed, so
+/// that every tuple over the elements found so far is eventually visited.
+/// Items of arity `0` are visited first, before the `known` initial elements are processed.
+/// `visit` is called with the item and the tuple, and returns the new number of known elements,
+/// or `None` to stop the exploration.
+pub(crate) fn explore_tuples<T, A, V>(items: &[T], arity: A, known: usize, mut visit: V) where A: Fn(&T) -> usize, V: FnMut(&T, &[usize]) -> Option<usize> {
+    let mut known = known;
+    let mut k = 0;
+    let mut constants = true;
+    while constants || k < known {
+        for item in items {
+            let arity = arity(item);
+            if constants != (arity == 0) {
+                continue
+            }
+
+            let positions = vec![(); arity];
+            for tuple in combinations(&positions, |_| 0..=k) {
+                if !constants && !tuple.contains(&k) {
+                    continue
+                }
+
+                match visit(item, &tuple) {
+                    Some(len) => known = len,
+                    None => return
+                }
+            }
+        }
+
+        if constants {
+            constants = false
+        } else {
+            k += 1
+        }
+    }
+}
+
 pub struct CombinationsOption<'a, T, F, I, E: Clone> where F: Fn(&'a T) -> I, I: 'a + Iterator<Item=E> {
     sources: &'a [T],
     f: F,
@@ -0,0 +1,36 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use terms::Term;
+use ta::{NoLabel, Rank};
+use ta::bottom_up::{Automaton, Configuration};
+
+/// Term whose symbol is ranked by its number of sub-terms, as parsed from a file.
+pub fn term(f: &str, sub_terms: Vec<Term<Rank<String>>>) -> Term<Rank<String>> {
+    let arity = sub_terms.len();
+    Term::new(Rank(f.to_string(), arity), sub_terms)
+}
+
+pub type Symbol = Rank<&'static str>;
+
+pub const ZERO: Symbol = Rank("0", 0);
+pub const S: Symbol = Rank("s", 1);
+
+pub fn nat(n: usize) -> Term<Symbol> {
+    let mut t = Term::new(ZERO, Vec::new());
+    for _ in 0..n {
+        t = Term::new(S, vec![t]);
+    }
+    t
+}
+
+/// Natural numbers that are multiples of `k`.
+pub fn multiples_of(k: u32) -> Automaton<Symbol, u32, NoLabel> {
+    let mut aut = Automaton::new();
+    aut.add(Configuration(ZERO, Vec::new()), NoLabel, 0);
+    for i in 0..k {
+        aut.add(Configuration(S, vec![i]), NoLabel, (i + 1) % k);
+    }
+    aut.set_final(0);
+    aut
+}
@@ -0,0 +1,122 @@
+#[macro_use]
+extern crate tree_automata as ta;
+extern crate terms;
+
+use terms::Term;
+use ta::{NoLabel, Rank};
+use ta::bottom_up::{Automaton, Configuration};
+
+type Symbol = Rank<&'static str>;
+
+const NIL: Symbol = Rank("nil", 0);
+const CONS: Symbol = Rank("cons", 2);
+const A: Symbol = Rank("a", 0);
+const B: Symbol = Rank("b", 0);
+
+const ALPHABET: [Symbol; 4] = [NIL, CONS, A, B];
+
+/// Lists of `a` and `b` containing at least one `a`, non-deterministically guessing its position.
+fn some_a() -> Automaton<Symbol, &'static str, NoLabel> {
+    automaton! {
+        NIL -> "any",
+        A -> "elt",
+        B -> "elt",
+        A -> "is_a",
+        CONS("elt", "any") -> "any",
+        CONS("is_a", "any") -> "found",
+        CONS("elt", "found") -> "found",
+        finals "found"
+    }
+}
+
+fn list(elements: &[Symbol]) -> Term<Symbol> {
+    let mut t = Term::new(NIL, Vec::new());
+    for e in elements.iter().rev() {
+        t = Term::new(CONS, vec![Term::new(*e, Vec::new()), t]);
+    }
+    t
+}
+
+fn samples() -> Vec<Term<Symbol>> {
+    vec![
+        list(&[]),
+        list(&[A]),
+        list(&[B]),
+        list(&[B, B]),
+        list(&[B, A, B]),
+        list(&[A, A]),
+        Term::new(CONS, vec![list(&[]), list(&[])])
+    ]
+}
+
+#[test]
+fn determinize() {
+    let aut = some_a();
+    let det = aut.determinize();
+
+    for (conf, _, _) in det.transitions() {
+        assert_eq!(det.states_for_configuration(conf).count(), 1);
+    }
+
+    for t in samples() {
+        assert_eq!(aut.accepts(&t), det.accepts(&t));
+    }
+}
+
+#[test]
+fn minimize() {
+    let aut = some_a();
+    let det = aut.determinize();
+    let (min, mapping) = det.minimize();
+
+    assert!(min.len() <= det.len());
+    for q in det.states() {
+        assert!(min.includes(&mapping[q]));
+    }
+
+    for t in samples() {
+        assert_eq!(aut.accepts(&t), min.accepts(&t));
+    }
+
+    // Two states recognizing every term are merged.
+    let dup: Automaton<Symbol, &'static str, NoLabel> = automaton! {
+        NIL -> "q1",
+        A -> "q1",
+        B -> "q2",
+        CONS("q1", "q1") -> "q1",
+        CONS("q1", "q2") -> "q2",
+        CONS("q2", "q1") -> "q1",
+        CONS("q2", "q2") -> "q2",
+        finals "q1" "q2"
+    };
+    let (min, mapping) = dup.minimize();
+    assert_eq!(min.len(), 1);
+    assert_eq!(mapping["q1"], mapping["q2"]);
+}
+
+#[test]
+fn complement() {
+    let aut = some_a();
+    let complement = aut.complement(ALPHABET.iter());
+
+    for t in samples() {
+        assert_eq!(aut.accepts(&t), !complement.accepts(&t));
+    }
+
+    // The complement of the complement is the original language.
+    let complement = complement.complement(ALPHABET.iter());
+    for t in samples() {
+        assert_eq!(aut.accepts(&t), complement.accepts(&t));
+    }
+}
+
+#[test]
+fn minimize_dangling_state() {
+    // "dead" only appears as a sub-state.
+    let mut aut = some_a();
+    aut.add(Configuration(CONS, vec!["dead", "any"]), NoLabel, "found");
+
+    let (min, mapping) = aut.minimize();
+    assert!(mapping.contains_key("dead"));
+    assert!(min.accepts(&list(&[B, A])));
+}
@@ -0,0 +1,71 @@
+extern crate tree_automata as ta;
+extern crate terms;
+
+use ta::{NoLabel, Rank};
+use ta::bottom_up::{Automaton, Configuration};
+
+mod common;
+
+use common::{Symbol, ZERO, S, nat};
+
+/// Natural numbers that are multiples of `k`, non-deterministically.
+fn multiples_of(k: u32) -> Automaton<Symbol, u32, NoLabel> {
+    let mut aut = common::multiples_of(k);
+    // Useless non-deterministic copy.
+    aut.add(Configuration(S, vec![0]), NoLabel, k);
+    aut.add(Configuration(S, vec![k]), NoLabel, k);
+    aut
+}
+
+#[test]
+fn included() {
+    let six = multiples_of(6);
+    let two = multiples_of(2);
+    let three = multiples_of(3);
+
+    assert!(six.is_included_in(&two).is_ok());
+    assert!(six.is_included_in(&three).is_ok());
+    assert!(six.is_included_in(&six).is_ok());
+    assert!(Automaton::<Symbol, u32, NoLabel>::new().is_included_in(&two).is_ok());
+}
+
+#[test]
+fn not_included() {
+    let two = multiples_of(2);
+    let three = multiples_of(3);
+
+    let t = two.is_included_in(&three).unwrap_err();
+    assert!(two.accepts(&t) && !three.accepts(&t));
+    assert_eq!(t, nat(2));
+
+    let t = three.is_included_in(&two).unwrap_err();
+    assert_eq!(t, nat(3));
+}
+
+#[test]
+fn equivalent() {
+    let two = multiples_of(2);
+    let three = multiples_of(3);
+    assert!(two.equivalent(&two.determinize()).is_ok());
+
+    let t = two.equivalent(&three).unwrap_err();
+    assert_ne!(two.accepts(&t), three.accepts(&t));
+
+    let t = three.equivalent(&two).unwrap_err();
+    assert_ne!(two.accepts(&t), three.accepts(&t));
+}
+
+#[test]
+fn universal() {
+    let alphabet = [ZERO, S];
+    let one = multiples_of(1);
+    assert!(one.is_universal(alphabet.iter()).is_ok());
+
+    let two = multiples_of(2);
+    assert_eq!(two.is_universal(alphabet.iter()).unwrap_err(), nat(1));
+
+    let mut complete = two.clone();
+    complete.set_final(1);
+    assert!(complete.is_universal(alphabet.iter()).is_ok());
+    assert!(complete.is_universal([ZERO, S, Rank("nil", 0)].iter()).is_err());
+}
@@ -0,0 +1,98 @@
+#[macro_use]
+extern crate tree_automata as ta;
+extern crate terms;
+
+use ta::{NoLabel, Inter, Pair, intersection};
+use ta::bottom_up::{Automaton, Configuration};
+
+mod common;
+
+use common::{Symbol, ZERO, S, nat, multiples_of};
+
+#[test]
+fn heterogeneous_intersection() {
+    let even = multiples_of(2);
+    let triple: Automaton<Symbol, &'static str, NoLabel> = automaton! {
+        ZERO -> "0",
+        S("0") -> "1",
+        S("1") -> "2",
+        S("2") -> "0",
+        finals "0"
+    };
+
+    let inter = intersection(&even, &triple);
+    assert!(inter.is_final(&Pair(0, "0")));
+    for n in 0..13 {
+        assert_eq!(inter.accepts(&nat(n)), n % 6 == 0);
+    }
+}
+
+#[test]
+fn n_ary_intersection() {
+    let even = multiples_of(2);
+    let triple = multiples_of(3);
+    let quadruple = multiples_of(4);
+
+    let inter = Automaton::inter(&[&even, &triple, &quadruple]);
+    for q in inter.final_states() {
+        assert_eq!(q.len(), 3);
+        assert_eq!(q.get(0), Some(&0));
+    }
+
+    for n in 0..25 {
+        assert_eq!(inter.accepts(&nat(n)), n % 12 == 0);
+    }
+}
+
+#[test]
+fn empty_intersection() {
+    let inter = Automaton::<Symbol, u32, NoLabel>::inter(&[]);
+    assert!(inter.is_empty());
+    assert_eq!(inter.len(), 0);
+}
+
+#[test]
+fn deep_intersection() {
+    let n = 20_000;
+    let long = multiples_of(n);
+    let mut any = multiples_of(1);
+    any.add(Configuration(S, vec![42]), NoLabel, 42);
+    any.set_final(42);
+
+    let inter = intersection(&long, &any);
+    assert_eq!(inter.len(), n as usize);
+    assert!(inter.states().all(|Pair(_, q)| *q == 0));
+    assert!(inter.is_final(&Pair(0, 0)));
+}
+
+#[test]
+fn union() {
+    // Both automata use the same state names.
+    let even = multiples_of(2);
+    let triple = multiples_of(3);
+
+    let union = Automaton::union(&[&even, &triple]);
+    assert_eq!(union.len(), 5);
+    for n in 0..13 {
+        assert_eq!(union.accepts(&nat(n)), n % 2 == 0 || n % 3 == 0);
+    }
+}
+
+#[test]
+fn difference() {
+    let even = multiples_of(2);
+    let triple = multiples_of(3);
+
+    let diff = ta::difference(&even, &triple);
+    for n in 0..13 {
+        assert_eq!(diff.accepts(&nat(n)), n % 2 == 0 && n % 3 != 0);
+    }
+
+    let sym = ta::symmetric_difference(&even, &triple);
+    for n in 0..13 {
+        assert_eq!(sym.accepts(&nat(n)), (n % 2 == 0) != (n % 3 == 0));
+    }
+
+    assert!(ta::difference(&even, &even).is_empty());
+    assert!(ta::symmetric_difference(&even, &even.determinize()).is_empty());
+}
@@ -0,0 +1,220 @@
+extern crate tree_automata as ta;
+extern crate terms;
+
+use ta::{Language, NoLabel, StateSet};
+use ta::bottom_up::{self, Configuration};
+use ta::alternating;
+
+mod common;
+
+use common::{Symbol, ZERO, S, nat, multiples_of};
+
+/// Checks the language operations of the given representation, with `a` and `b` recognizing
+/// multiples of 2 and 3.
+fn check<L: Language<Symbol>>(a: &L, b: &L) {
+    assert!(a.accepts(&nat(4)) && !a.accepts(&nat(3)));
+    assert!(!a.is_empty());
+    assert!(a.accepts(&a.witness().unwrap()));
+
+    let inter = a.intersect(b);
+    assert!(!inter.is_empty());
+    for n in 0..13 {
+        assert_eq!(inter.accepts(&nat(n)), n % 6 == 0);
+    }
+
+    let union = a.union(b);
+    for n in 0..13 {
+        assert_eq!(union.accepts(&nat(n)), n % 2 == 0 || n % 3 == 0);
+    }
+}
+
+#[test]
+fn bottom_up_language() {
+    check(&multiples_of(2), &multiples_of(3))
+}
+
+#[test]
+fn alternating_language() {
+    let a = alternating::Automaton::from(&multiples_of(2));
+    let b = alternating::Automaton::from(&multiples_of(3));
+    check(&a, &b)
+}
+
+/// 0 recognizes multiples of 6, as multiples of both 2 (states 2x) and 3 (states 3x).
+fn multiples_of_six() -> alternating::Automaton<Symbol, u32, u32> {
+    let mut aut = alternating::Automaton::new();
+    aut.add(&0, &ZERO, Vec::new());
+    aut.add(&0, &S, vec![(0, 21), (0, 32)]);
+    aut.add(&20, &ZERO, Vec::new());
+    aut.add(&20, &S, vec![(0, 21)]);
+    aut.add(&21, &S, vec![(0, 20)]);
+    aut.add(&30, &ZERO, Vec::new());
+    aut.add(&30, &S, vec![(0, 32)]);
+    aut.add(&31, &S, vec![(0, 30)]);
+    aut.add(&32, &S, vec![(0, 31)]);
+    aut.set_initial(0);
+    aut
+}
+
+#[test]
+fn alternating_membership() {
+    let mut aut = multiples_of_six();
+    for n in 0..13 {
+        assert_eq!(aut.accepts(&nat(n)), n % 6 == 0);
+    }
+    assert!(aut.state_accepts(&21, &nat(3)));
+
+    // Out of range indexes are never satisfied.
+    aut.add(&1, &S, vec![(1, 20)]);
+    aut.set_initial(1);
+    assert!(!aut.accepts(&nat(1)));
+}
+
+#[test]
+fn alternating_to_bottom_up() {
+    let alt = multiples_of_six();
+
+    let aut: bottom_up::Automaton<Symbol, StateSet<_>, NoLabel> = (&alt).into();
+    for n in 0..13 {
+        assert_eq!(aut.accepts(&nat(n)), n % 6 == 0);
+    }
+
+    // Each state is the set of alternating states recognizing the same terms.
+    for n in 0..13 {
+        for set in aut.run(&nat(n)) {
+            let set = set.0;
+            for q in alt.states() {
+                assert_eq!(set.contains(q), alt.state_accepts(q, &nat(n)))
+            }
+        }
+    }
+}
+
+#[test]
+fn alternating_emptiness() {
+    let mut aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::new();
+    // 0 recognizes numbers that are both even and odd.
+    aut.add(&0, &S, vec![(0, 1), (0, 2)]);
+    aut.add(&1, &ZERO, Vec::new());
+    aut.add(&1, &S, vec![(0, 2)]);
+    aut.add(&2, &S, vec![(0, 1)]);
+    aut.set_initial(0);
+    assert!(aut.is_empty());
+
+    aut.add(&0, &ZERO, Vec::new());
+    assert_eq!(aut.witness(), Some(nat(0)));
+}
+
+#[test]
+fn alternating_witness_height() {
+    let mut aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::new();
+    // The state `base + i` recognizes the numbers equal to `i` modulo `k`.
+    for &(base, k) in [(40, 4), (60, 6)].iter() {
+        aut.add(&base, &ZERO, Vec::new());
+        for i in 0..k {
+            aut.add(&(base + i), &S, vec![(0, base + (i + k - 1) % k)]);
+        }
+    }
+
+    // 0 recognizes the positive multiples of both 4 and 6: the smallest witness is then 12.
+    aut.add(&0, &S, vec![(0, 43), (0, 65)]);
+    aut.set_initial(0);
+    assert_eq!(aut.witness(), Some(nat(12)));
+
+    aut.add(&0, &ZERO, Vec::new());
+    assert_eq!(aut.witness(), Some(nat(0)));
+}
+
+#[test]
+fn alternating_boolean() {
+    let a = alternating::Automaton::from(&multiples_of(2));
+    let b = alternating::Automaton::from(&multiples_of(3));
+
+    let inter = a.and(&b);
+    let union = a.or(&b);
+    for n in 0..13 {
+        assert_eq!(inter.accepts(&nat(n)), n % 6 == 0);
+        assert_eq!(union.accepts(&nat(n)), n % 2 == 0 || n % 3 == 0);
+    }
+
+    let aut: bottom_up::Automaton<Symbol, StateSet<_>, NoLabel> = (&inter).into();
+    for n in 0..13 {
+        assert_eq!(aut.accepts(&nat(n)), n % 6 == 0);
+    }
+}
+
+#[test]
+fn alternating_negation() {
+    let alphabet = [ZERO, S];
+    let a = alternating::Automaton::from(&multiples_of(2));
+    let b = alternating::Automaton::from(&multiples_of(3));
+
+    let not_a = a.negate(&alphabet);
+    for n in 0..13 {
+        assert_eq!(not_a.accepts(&nat(n)), n % 2 != 0);
+    }
+
+    // Neither a multiple of 2 nor of 3.
+    let neither = a.or(&b).negate(&alphabet);
+    for n in 0..13 {
+        assert_eq!(neither.accepts(&nat(n)), n % 2 != 0 && n % 3 != 0);
+    }
+    assert_eq!(neither.witness(), Some(nat(1)));
+
+    // Double negation.
+    let a_again = not_a.negate(&alphabet);
+    for n in 0..13 {
+        assert_eq!(a_again.accepts(&nat(n)), n % 2 == 0);
+    }
+}
+
+#[test]
+fn alternating_simplify() {
+    let mut aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::new();
+    aut.add(&0, &ZERO, Vec::new());
+    aut.add(&0, &S, vec![(0, 1), (0, 1)]);
+    aut.add(&0, &S, vec![(0, 1), (0, 2)]); // subsumed.
+    aut.add(&0, &S, vec![(0, 1)]); // duplicate.
+    aut.add(&0, &S, vec![(0, 3)]); // 3 has no clause.
+    aut.add(&1, &S, vec![(0, 0)]);
+    aut.add(&2, &ZERO, Vec::new());
+    aut.add(&4, &S, vec![(0, 3)]); // false, and unreachable.
+    aut.add(&5, &ZERO, Vec::new()); // unreachable.
+    aut.set_initial(0);
+
+    let removed = aut.simplify();
+    assert_eq!(removed, alternating::Simplification { conjunctions: 6, states: 3 });
+    assert_eq!(aut.states().count(), 2);
+    for n in 0..6 {
+        assert_eq!(aut.accepts(&nat(n)), n % 2 == 0);
+    }
+
+    assert_eq!(aut.simplify(), alternating::Simplification::default());
+}
+
+#[test]
+fn alternating_to_bottom_up_dead_state() {
+    // State 2 only appears as a sub-state, so it has no clause in the alternating automaton.
+    let mut aut = multiples_of(2);
+    aut.add(Configuration(S, vec![2]), NoLabel, 0);
+    let alternating = alternating::Automaton::from(&aut);
+
+    let converted: bottom_up::Automaton<Symbol, StateSet<_>, NoLabel> = (&alternating).into();
+    for n in 0..6 {
+        assert_eq!(converted.accepts(&nat(n)), n % 2 == 0);
+    }
+}
+
+#[test]
+fn alternating_emptiness_dead_state() {
+    let mut aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::new();
+    // State 7 has no clause, hence is false.
+    aut.add(&0, &S, vec![(0, 7)]);
+    aut.set_initial(0);
+    assert!(aut.is_empty());
+    assert!(Language::is_empty(&aut));
+
+    aut.add(&0, &S, vec![(0, 1)]);
+    aut.add(&1, &ZERO, Vec::new());
+    assert_eq!(aut.witness(), Some(nat(1)));
+}
@@ -0,0 +1,160 @@
+#[macro_use]
+extern crate tree_automata as ta;
+extern crate terms;
+
+use terms::Term;
+use ta::{NoLabel, intersection};
+use ta::bottom_up::{Automaton, Configuration};
+
+fn even_lists() -> Automaton<&'static str, &'static str, NoLabel> {
+    automaton! {
+        "nil" -> "even",
+        "cons"("even") -> "odd",
+        "cons"("odd") -> "even",
+        finals "even"
+    }
+}
+
+fn list(n: usize) -> Term<&'static str> {
+    let mut t = Term::new("nil", Vec::new());
+    for _ in 0..n {
+        t = Term::new("cons", vec![t]);
+    }
+    t
+}
+
+#[test]
+fn run_states() {
+    let aut = even_lists();
+    assert!(aut.run(&list(3)).contains(&("odd", NoLabel)));
+    assert!(aut.run(&Term::new("foo", Vec::new())).is_empty());
+}
+
+#[test]
+fn accepts() {
+    let aut = even_lists();
+    assert!(aut.accepts(&list(0)));
+    assert!(!aut.accepts(&list(1)));
+    assert!(aut.accepts(&list(4)));
+}
+
+#[test]
+fn witness() {
+    let aut = even_lists();
+    assert_eq!(aut.witness(), Some(list(0)));
+
+    let aut_odd: Automaton<&'static str, &'static str, NoLabel> = automaton! {
+        "nil" -> "even",
+        "cons"("even") -> "odd",
+        "cons"("odd") -> "even",
+        finals "odd"
+    };
+    assert_eq!(aut_odd.witness(), Some(list(1)));
+
+    let mut only_dead: Automaton<&'static str, &'static str, NoLabel> = automaton! {
+        "loop"("dead") -> "dead",
+        finals "dead"
+    };
+    assert!(only_dead.is_empty());
+    only_dead.add(Configuration("nil", Vec::new()), NoLabel, "dead");
+    assert_eq!(only_dead.witness(), Some(list(0)));
+}
+
+#[test]
+fn trim() {
+    let mut aut: Automaton<&'static str, &'static str, NoLabel> = automaton! {
+        "nil" -> "even",
+        "cons"("even") -> "odd",
+        "cons"("odd") -> "even",
+        "loop"("dead") -> "dead",
+        "pair"("even", "dead") -> "even",
+        "nil" -> "unused",
+        finals "even"
+    };
+
+    let productive = aut.productive_states();
+    assert!(productive.contains("odd") && productive.contains("unused"));
+    assert!(!productive.contains("dead"));
+
+    let accessible = aut.accessible_states();
+    assert!(accessible.contains("dead"));
+    assert!(!accessible.contains("unused"));
+
+    aut.trim();
+    let mut states: Vec<_> = aut.states().cloned().collect();
+    states.sort();
+    assert_eq!(states, vec!["even", "odd"]);
+    assert_eq!(aut.transitions().count(), 3);
+    assert!(aut.accepts(&list(2)));
+}
+
+#[test]
+fn epsilon_transitions() {
+    let mut aut = even_lists();
+    aut.add(Configuration("cons", vec!["any"]), NoLabel, "any");
+    aut.add_epsilon("even", "any");
+    aut.add_epsilon("any", "list");
+    aut.set_final("list");
+
+    assert!(aut.run(&list(1)).contains(&("list", NoLabel)));
+    assert!(aut.accepts(&list(3)));
+
+    aut.eliminate_epsilons();
+    assert_eq!(aut.epsilon_transitions().count(), 0);
+    assert!(aut.configurations_for_state(&"list").any(|(conf, _)| conf.symbol() == &"nil"));
+    assert!(aut.accepts(&list(3)));
+    assert!(aut.witness().is_some());
+}
+
+#[test]
+fn epsilon_analyses() {
+    let a = Term::new("a", Vec::new());
+    let mut aut = Automaton::new();
+    aut.add(Configuration("a", Vec::new()), NoLabel, "p");
+    aut.add_epsilon("p", "q");
+    aut.set_final("q");
+
+    assert!(aut.accepts(&a));
+    assert!(!aut.is_empty());
+    assert_eq!(aut.witness(), Some(a.clone()));
+
+    let mut trimmed = aut.clone();
+    trimmed.trim();
+    assert!(trimmed.accepts(&a));
+
+    let empty: Automaton<&str, &str, NoLabel> = Automaton::new();
+    assert_eq!(aut.is_included_in(&empty), Err(a.clone()));
+    assert_eq!(empty.is_included_in(&aut), Ok(()));
+
+    assert!(aut.determinize().accepts(&a));
+    assert!(aut.minimize().0.accepts(&a));
+    assert!(aut.reduce_by_simulation().0.accepts(&a));
+    assert!(intersection(&aut, &aut).accepts(&a));
+}
+
+#[test]
+fn epsilon_union() {
+    let a = Term::new("a", Vec::new());
+    let mut aut = Automaton::new();
+    aut.add(Configuration("a", Vec::new()), NoLabel, "p");
+    aut.add_epsilon("p", "q");
+    aut.set_final("q");
+
+    assert!(Automaton::union(&[&aut, &even_lists()]).accepts(&a));
+    assert!(ta::alternating::Automaton::from(&aut).accepts(&a));
+}
+
+#[test]
+fn epsilon_search() {
+    let a = Term::new("a", Vec::new());
+    let mut aut = Automaton::new();
+    aut.add(Configuration("a", Vec::new()), NoLabel, "p");
+    aut.add_epsilon("p", "q");
+    aut.set_final("q");
+
+    assert_eq!(aut.representatives().collect::<Vec<_>>(), vec![a]);
+
+    let automata = [&aut, &aut];
+    let positions = ["q", "q"];
+    assert_eq!(Automaton::common_configurations(&automata, &positions).count(), 1);
+}
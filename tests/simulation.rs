@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate tree_automata as ta;
+extern crate terms;
+
+use terms::Term;
+use ta::NoLabel;
+use ta::bottom_up::{Automaton, Configuration};
+
+fn list(elements: &[&'static str]) -> Term<&'static str> {
+    let mut t = Term::new("nil", Vec::new());
+    for e in elements.iter().rev() {
+        t = Term::new("cons", vec![Term::new(*e, Vec::new()), t]);
+    }
+    t
+}
+
+/// Lists of `a` and `b`, with redundant states and transitions.
+fn lists() -> Automaton<&'static str, &'static str, NoLabel> {
+    automaton! {
+        "a" -> "a",
+        "a" -> "ab",
+        "b" -> "ab",
+        "a" -> "ab'",
+        "b" -> "ab'",
+        "nil" -> "list",
+        "nil" -> "list'",
+        "cons"("a", "list") -> "list",
+        "cons"("ab", "list") -> "list",
+        "cons"("ab'", "list'") -> "list'",
+        "cons"("ab", "list'") -> "list",
+        finals "list" "list'"
+    }
+}
+
+#[test]
+fn downward_simulation() {
+    let aut = lists();
+    let simulation = aut.downward_simulation();
+
+    assert!(simulation.contains(&("a", "ab")));
+    assert!(!simulation.contains(&("ab", "a")));
+    assert!(simulation.contains(&("ab", "ab'")) && simulation.contains(&("ab'", "ab")));
+    assert!(simulation.contains(&("list", "list'")) && simulation.contains(&("list'", "list")));
+
+    let upward = aut.upward_simulation(&simulation);
+    assert!(upward.contains(&("a", "ab")));
+    assert!(!upward.contains(&("list", "a")));
+}
+
+#[test]
+fn reduce_by_simulation() {
+    let aut = lists();
+    let (reduced, mapping) = aut.reduce_by_simulation();
+
+    assert_eq!(mapping["ab"], mapping["ab'"]);
+    assert_eq!(mapping["list"], mapping["list'"]);
+    assert_eq!(reduced.len(), 3);
+
+    // The transition `cons(a, list) -> list` is subsumed by `cons(ab, list) -> list`.
+    assert_eq!(reduced.transitions().count(), 5);
+
+    assert!(reduced.equivalent(&aut).is_ok());
+    assert!(reduced.accepts(&list(&["a", "b"])));
+}
+
+#[test]
+fn reduce_dangling_state() {
+    // "dead" only appears as a sub-state, and recognizes no term.
+    let mut aut = lists();
+    aut.add(Configuration("cons", vec!["dead", "list"]), NoLabel, "list");
+
+    let (reduced, mapping) = aut.reduce_by_simulation();
+    assert!(mapping.contains_key("dead"));
+    assert!(reduced.equivalent(&aut).is_ok());
+}
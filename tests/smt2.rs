@@ -0,0 +1,96 @@
+extern crate tree_automata as ta;
+
+extern crate terms;
+
+use ta::alternating;
+use ta::smt2;
+
+mod common;
+
+use common::{Symbol, ZERO, S, multiples_of, term};
+
+#[test]
+fn symbols() {
+    assert_eq!(smt2::symbol("cons"), "cons");
+    assert_eq!(smt2::symbol("x.0"), "x.0");
+    assert_eq!(smt2::symbol("0"), "|0|");
+    assert_eq!(smt2::symbol("a b"), "|a b|");
+    assert_eq!(smt2::symbol("a|b"), "|a_b|");
+}
+
+#[test]
+fn chc() {
+    let aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::from(&multiples_of(2));
+    assert_eq!(smt2::chc(&aut, "Nat"), "(set-logic HORN)
+(declare-datatypes ((Nat 0)) (((|0:0|) (|s:1| (|s:1.0| Nat)))))
+(declare-fun q_0 (Nat) Bool)
+(declare-fun q_1 (Nat) Bool)
+(assert (q_0 |0:0|))
+(assert (forall ((x0 Nat)) (=> (q_1 x0) (q_0 (|s:1| x0)))))
+(assert (forall ((x0 Nat)) (=> (q_0 x0) (q_1 (|s:1| x0)))))
+(assert (forall ((x Nat)) (=> (q_0 x) false)))
+(check-sat)
+");
+}
+
+#[test]
+fn chc_conjunctions() {
+    let mut aut: alternating::Automaton<Symbol, u32, u32> = alternating::Automaton::new();
+    aut.add(&0, &S, vec![(0, 1), (0, 2)]);
+    aut.add(&0, &S, Vec::new());
+    aut.add(&0, &S, vec![(1, 1)]); // out of range.
+    aut.add(&1, &ZERO, Vec::new());
+    aut.set_initial(0);
+
+    assert_eq!(smt2::chc(&aut, "T"), "(set-logic HORN)
+(declare-datatypes ((T 0)) (((|0:0|) (|s:1| (|s:1.0| T)))))
+(declare-fun q_0 (T) Bool)
+(declare-fun q_1 (T) Bool)
+(declare-fun q_2 (T) Bool)
+(assert (forall ((x0 T)) (=> (and (q_1 x0) (q_2 x0)) (q_0 (|s:1| x0)))))
+(assert (forall ((x0 T)) (q_0 (|s:1| x0))))
+(assert (q_1 |0:0|))
+(assert (forall ((x T)) (=> (q_0 x) false)))
+(check-sat)
+");
+}
+
+#[test]
+fn datatypes() {
+    let universes = smt2::parse_datatypes("
+        ; Lists of booleans.
+        (declare-datatypes ((B 0) (L 0)) (
+            ((true) (false))
+            ((nil) (cons (head B) (tail L)))
+        ))
+        (declare-fun x () L)
+        (declare-datatype |Pair of lists| ((pair (fst L) (snd L))))
+    ").unwrap();
+    assert_eq!(universes.len(), 3);
+
+    let b = &universes["B"];
+    assert!(b.accepts(&term("true", vec![])));
+    assert!(!b.accepts(&term("nil", vec![])));
+    assert_eq!(b.states().count(), 1);
+
+    let list = term("cons", vec![term("false", vec![]), term("nil", vec![])]);
+    assert!(universes["L"].accepts(&list));
+    assert!(!universes["L"].accepts(&term("cons", vec![term("nil", vec![]), term("nil", vec![])])));
+    assert!(universes["Pair of lists"].accepts(&term("pair", vec![list.clone(), list])));
+}
+
+#[test]
+fn legacy_datatypes() {
+    let universes = smt2::parse_datatypes("(declare-datatypes () ((Nat zero (succ (pred Nat)))))").unwrap();
+    let nat = &universes["Nat"];
+    assert!(nat.accepts(&term("succ", vec![term("zero", vec![])])));
+}
+
+#[test]
+fn datatype_errors() {
+    assert_eq!(smt2::parse_datatypes("(declare-datatype T ((a))").err(), Some(smt2::Error::UnexpectedEnd));
+    assert_eq!(smt2::parse_datatypes("(declare-datatype T ((a))))").err(), Some(smt2::Error::UnexpectedClose(26)));
+    assert_eq!(smt2::parse_datatypes("(declare-datatype T ((a (x Int))))").err(), Some(smt2::Error::UndefinedSort("Int".to_string())));
+    assert_eq!(smt2::parse_datatypes("(declare-datatypes ((L 1)) ((par (T) ((nil)))))").err(), Some(smt2::Error::Parametric("L".to_string())));
+    assert_eq!(smt2::parse_datatypes("(declare-datatype T a)").err(), Some(smt2::Error::InvalidDeclaration("(declare-datatype T a)".to_string())));
+}
@@ -0,0 +1,70 @@
+extern crate tree_automata as ta;
+extern crate terms;
+
+use ta::timbuk;
+
+mod common;
+
+use common::term;
+
+const LISTS: &str = "Ops nil:0 cons:2 a:0 b:0
+Automaton lists
+States q0 q1:0 q2
+Final States q1
+Transitions
+a -> q0
+b() -> q2
+q2 -> q0
+nil -> q1
+cons(q0, q1) -> q1
+";
+
+#[test]
+fn read() {
+    let aut = timbuk::parse(LISTS).unwrap();
+    let list = term("cons", vec![term("b", vec![]), term("cons", vec![term("a", vec![]), term("nil", vec![])])]);
+    assert!(aut.accepts(&list));
+    assert!(!aut.accepts(&term("cons", vec![term("nil", vec![]), term("nil", vec![])])));
+    assert_eq!(aut.epsilon_transitions().count(), 1);
+}
+
+#[test]
+fn write() {
+    let aut = timbuk::parse(LISTS).unwrap();
+    let written = timbuk::to_string(&aut, "lists");
+    assert_eq!(written, "Ops a:0 b:0 cons:2 nil:0
+Automaton lists
+States q0 q1 q2
+Final States q1
+Transitions
+a -> q0
+b -> q2
+cons(q0,q1) -> q1
+nil -> q1
+q2 -> q0
+");
+
+    // `b` is only accepted through the epsilon transition `q2 -> q0`.
+    let read = timbuk::parse(&written).unwrap();
+    assert!(read.accepts(&term("cons", vec![term("b", vec![]), term("nil", vec![])])));
+    assert!(read.equivalent(&aut).is_ok());
+}
+
+#[test]
+fn epsilon_equivalence() {
+    let header = "Ops a:0 b:0\nAutomaton A\nStates p q\nFinal States q\nTransitions\na -> q\nb -> p\n";
+    let with_epsilon = timbuk::parse(&format!("{}p -> q\n", header)).unwrap();
+    let without_epsilon = timbuk::parse(header).unwrap();
+
+    assert!(with_epsilon.accepts(&term("b", vec![])));
+    assert_eq!(with_epsilon.equivalent(&without_epsilon), Err(term("b", vec![])));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(timbuk::parse("Ops a:0\nAutomaton A\nStates q\nFinal States q\nTransitions\nb -> q").err(), Some(timbuk::Error::UndeclaredSymbol("b".to_string(), 6)));
+    assert_eq!(timbuk::parse("Ops a:0\nAutomaton A\nStates q\nFinal States p\nTransitions").err(), Some(timbuk::Error::UndeclaredState("p".to_string(), 4)));
+    assert_eq!(timbuk::parse("Ops a:0\nAutomaton A\nStates q\nFinal States q\nTransitions\na(q) -> q").err(), Some(timbuk::Error::InvalidArity("a".to_string(), 6)));
+    assert_eq!(timbuk::parse("Ops a\nAutomaton A").err(), Some(timbuk::Error::InvalidSymbol("a".to_string(), 1)));
+    assert_eq!(timbuk::parse("Ops a:0\nAutomaton A\nStates q\nFinal States q\nTransitions\na -> ").err(), Some(timbuk::Error::UnexpectedEnd));
+}
@@ -0,0 +1,41 @@
+#[macro_use]
+extern crate tree_automata as ta;
+extern crate terms;
+
+use terms::Term;
+use ta::{NoLabel, Transpose, Permutations};
+use ta::bottom_up::{Automaton, Configuration};
+
+fn leaf(f: &'static str) -> Term<&'static str> {
+    Term::new(f, Vec::new())
+}
+
+#[test]
+fn swap_arguments() {
+    let aut: Automaton<&'static str, &'static str, NoLabel> = automaton! {
+        "a" -> "a",
+        "b" -> "b",
+        "pair"("a", "b") -> "ab",
+        "f"("ab") -> "f",
+        finals "f"
+    };
+
+    let mut permutations = Permutations::new();
+    assert_eq!(permutations.set("pair", vec![1, 0]), Ok(()));
+    let transposed = aut.transpose(&permutations);
+
+    let original = Term::new("f", vec![Term::new("pair", vec![leaf("a"), leaf("b")])]);
+    let swapped = Term::new("f", vec![Term::new("pair", vec![leaf("b"), leaf("a")])]);
+
+    assert!(aut.accepts(&original) && !aut.accepts(&swapped));
+    assert!(transposed.accepts(&swapped) && !transposed.accepts(&original));
+    assert!(transposed.transpose(&permutations).equivalent(&aut).is_ok());
+}
+
+#[test]
+fn invalid_permutation() {
+    let mut permutations = Permutations::new();
+    assert_eq!(permutations.set("pair", vec![1, 1]), Err(vec![1, 1]));
+    assert_eq!(permutations.set("pair", vec![0, 2]), Err(vec![0, 2]));
+    assert_eq!(permutations.get(&"pair"), None);
+}